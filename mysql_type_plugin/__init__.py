@@ -20,10 +20,9 @@ from mypy.types import (
     TypedDictType,
     ARG_POS
 )
-from mypy.nodes import StrExpr, OpExpr, Expression, Context
+from mypy.nodes import StrExpr, OpExpr, Expression, Context, MypyFile, ARG_NAMED
 from mypy.errorcodes import ErrorCode
-import re
-import mysql_type_plugin.mysql_type_plugin as rs  # type: ignore
+import mysql_type_plugin.mysql_type_plugin as rs
 
 
 def get_str_value(e: Expression) -> Optional[str]:
@@ -57,7 +56,7 @@ def get_schemas(api: CheckerPluginInterface, context: Context) -> Any:
         api.fail(f"Unable to read mysql-type-schema.sql: {e}", context)
         return None
 
-    (s, err, message) = rs.parse_schemas("mysql-type-schema.sql", src)
+    (s, err, message, _issues) = rs.parse_schemas("mysql-type-schema.sql", src)
     if err:
         api.fail(message, context)
     elif message:
@@ -97,11 +96,11 @@ def type_statement(
     except Exception as e:
         api.fail(f"ICE {sql} {e}", context)
         return None
-    if len(a) != 3:
+    if len(a) != 4:
         api.fail(f"ICE {a}", context)
         return None
 
-    (stmt, err, message) = a
+    (stmt, err, message, _issues) = a
     if err and not quiet:
         api.fail(message, context)
     elif message and not quiet:
@@ -110,40 +109,74 @@ def type_statement(
     return stmt
 
 
+def get_argument_type(
+    v: Any, not_null: bool, api: CheckerPluginInterface, context: Context
+) -> Type:
+    t: Type = AnyType(TypeOfAny.special_form)
+    if isinstance(v, rs.Integer):
+        t = api.named_generic_type("int", [])
+    elif isinstance(v, rs.Float):
+        t = api.named_generic_type("float", [])
+    elif isinstance(v, rs.String):
+        t = api.named_generic_type("str", [])
+    elif isinstance(v, rs.Bool):
+        t = api.named_generic_type("bool", [])
+    elif isinstance(v, rs.Bytes):
+        t = api.named_generic_type("bytes", [])
+    elif isinstance(v, rs.Date):
+        t = api.named_generic_type("datetime.date", [])
+    elif isinstance(v, (rs.DateTime, rs.Timestamp)):
+        t = api.named_generic_type("datetime.datetime", [])
+    elif isinstance(v, rs.Time):
+        t = api.named_generic_type("datetime.timedelta", [])
+    elif isinstance(v, rs.Json):
+        t = AnyType(TypeOfAny.special_form)  # str or a serializable value
+    elif isinstance(v, rs.Enum):
+        t = api.named_generic_type("str", [])  # TODO literal with values
+    elif isinstance(v, rs.Set):
+        t = api.named_generic_type("str", [])  # TODO validate members
+    elif isinstance(v, rs.List):
+        t = api.named_generic_type(
+            "typing.Sequence", [get_argument_type(v.element, v.not_null, api, context)]
+        )
+    elif not isinstance(v, rs.SqlType):
+        api.fail(f"Unknown type {v}", context)
+    if not not_null:
+        t = UnionType((t, NoneType()))
+    return t
+
+
 def get_argument_types(
     stmt: Any, api: CheckerPluginInterface, context: Context
 ) -> List[Type]:
     ts: List[Type] = []
-    if args := getattr(stmt, "arguments", None):
-        for k, (v, not_null) in args.items():
-            if not isinstance(k, int):
-                continue
-            while len(ts) <= k:
-                ts.append(AnyType(TypeOfAny.special_form))
-            t: Type = AnyType(TypeOfAny.special_form)
-            if isinstance(v, rs.Integer):
-                t = api.named_generic_type("int", [])
-            elif isinstance(v, rs.Float):
-                t = api.named_generic_type("float", [])
-            elif isinstance(v, rs.String):
-                t = api.named_generic_type("str", [])
-            elif isinstance(v, rs.Bool):
-                t = api.named_generic_type("bool", [])
-            elif isinstance(v, rs.Bytes):
-                t = api.named_generic_type("bytes", [])
-            elif isinstance(v, rs.Enum):
-                t = api.named_generic_type("str", [])  # TODO literal with values
-            elif isinstance(v, rs.Any):
-                t = AnyType(TypeOfAny.special_form)
-            else:
-                api.fail(f"Unknown type {v}", context)
-            if not not_null:
-                t = UnionType((t, NoneType()))
-            ts[k] = t
+    if signature := getattr(stmt, "signature", None):
+        if signature.named:
+            return ts
+        for arg in signature.arguments:
+            ts.append(get_argument_type(arg.type, arg.not_null, api, context))
+    return ts
+
+
+def get_named_argument_types(
+    stmt: Any, api: CheckerPluginInterface, context: Context
+) -> Optional["OrderedDict[str, Type]"]:
+    signature = getattr(stmt, "signature", None)
+    if signature is None or not signature.named:
+        return None
+    ts: "OrderedDict[str, Type]" = OrderedDict()
+    for name, (v, not_null) in signature.types.items():
+        if isinstance(name, str):
+            ts[name] = get_argument_type(v, not_null, api, context)
     return ts
 
 
 class CustomPlugin(Plugin):
+    def get_additional_deps(self, file: MypyFile) -> List[Tuple[int, str, int]]:
+        # Argument and column types refer to datetime.date and friends, so datetime must
+        # be loaded even when the checked module does not import it
+        return [(10, "datetime", -1)]
+
     def get_function_signature_hook(
         self, fullname: str
     ) -> Optional[Callable[[FunctionSigContext], CallableType]]:
@@ -160,8 +193,7 @@ class CustomPlugin(Plugin):
                 if sql is None:
                     return context.default_signature
 
-                sql2 = sql.replace("_LIST_", "%s, %s, %s")
-                stmt = type_statement(sql2, context.api, context.context, dict_cursor=False, quiet=True)
+                stmt = type_statement(sql, context.api, context.context, dict_cursor=False, quiet=True)
                 if stmt is None:
                     return context.default_signature
                 ans = CallableType(
@@ -176,24 +208,19 @@ class CustomPlugin(Plugin):
                     variables=context.default_signature.variables,
                 )
 
+                if (named := get_named_argument_types(stmt, context.api, context.context)) is not None:
+                    for name, t in named.items():
+                        ans.arg_types.append(t)
+                        ans.arg_names.append(name)
+                        ans.arg_kinds.append(ARG_NAMED)
+                    return ans
+
                 at = get_argument_types(stmt, context.api, context.context)
 
-                ati = iter(at)
-                for i, a in enumerate(re.findall("(_LIST_|[%]s|[%]\()", sql)):
-                    try:
-                        if a == "_LIST_":
-                            t = context.api.named_generic_type(
-                                "typing.Sequence", [next(ati)]
-                            )
-                            next(ati)
-                            next(ati)
-                        else:
-                            t = next(ati)
-                        ans.arg_types.append(t)
-                        ans.arg_names.append(f"a{i}")
-                        ans.arg_kinds.append(ARG_POS)
-                    except StopIteration:
-                        context.api.fail(f"Argument match failed {i}", context.context)
+                for i, t in enumerate(at):
+                    ans.arg_types.append(t)
+                    ans.arg_names.append(f"a{i}")
+                    ans.arg_kinds.append(ARG_POS)
                 return ans
             except Exception as e:
                 context.api.fail(f"ICE: {e}", context.context)
@@ -229,14 +256,15 @@ class CustomPlugin(Plugin):
                 sql = get_sql(1, context.args, api, quiet=False)
                 if sql is None:
                     return context.default_return_type
-                sql = sql.replace("_LIST_", "%s")
                 stmt = type_statement(sql, api, context.context, dict_cursor=dc,quiet=False)
                 if stmt is None:
                     return context.default_return_type
 
-                if isinstance(stmt, rs.Select):
+                returning = isinstance(stmt, (rs.Insert, rs.Replace, rs.Delete)) and stmt.columns
+                if isinstance(stmt, rs.Select) or returning:
                     ntp: List[Tuple[str, Type]] = []
-                    for (name, type_, not_null) in stmt.columns:
+                    for column in stmt.columns:
+                        name, type_, not_null = column.name, column.type, column.not_null
                         t: Type
                         if isinstance(type_, rs.Integer):
                             t = api.named_generic_type("int", [])
@@ -250,12 +278,22 @@ class CustomPlugin(Plugin):
                             t = api.named_generic_type(
                                 "str", []
                             )  # TODO literal with values
+                        elif isinstance(type_, rs.Set):
+                            t = api.named_generic_type("str", [])
                         elif isinstance(type_, rs.Bytes):
                             t = api.named_generic_type("bytes", [])
-                        elif isinstance(type_, rs.Any):
-                            t = AnyType(TypeOfAny.special_form)
+                        elif isinstance(type_, rs.Date):
+                            t = api.named_generic_type("datetime.date", [])
+                        elif isinstance(type_, (rs.DateTime, rs.Timestamp)):
+                            t = api.named_generic_type("datetime.datetime", [])
+                        elif isinstance(type_, rs.Time):
+                            t = api.named_generic_type("datetime.timedelta", [])
+                        elif isinstance(type_, rs.Json):
+                            t = api.named_generic_type("str", [])
                         else:
-                            api.fail(f"Unknown type {type_}", context.context)
+                            t = AnyType(TypeOfAny.special_form)
+                            if not isinstance(type_, rs.SqlType):
+                                api.fail(f"Unknown type {type_}", context.context)
                         if not not_null:
                             t = UnionType((t, NoneType()))
                         ntp.append((name, t))
@@ -278,12 +316,12 @@ class CustomPlugin(Plugin):
                         context.api.fail(f"Could not find mysql_type.SelectResult", ct)
 
                 elif isinstance(stmt, rs.Insert):
-                    if stmt.yield_autoincrement == "yes":
+                    if stmt.yield_autoincrement == rs.AutoIncrement.Yes:
                         if ir := self.lookup_fully_qualified(
                             "mysql_type.InsertWithLastRowIdResult"
                         ):
                             return Instance(ir.node, [])  # type: ignore
-                    elif stmt.yield_autoincrement == "maybe":
+                    elif stmt.yield_autoincrement == rs.AutoIncrement.Maybe:
                         if ir := self.lookup_fully_qualified(
                             "mysql_type.InsertWithOptLastRowIdResult"
                         ):
@@ -330,15 +368,21 @@ class CustomPlugin(Plugin):
                     context.default_signature.ret_type,
                     context.default_signature.fallback,
                 )
-                ts = get_argument_types(stmt, context.api, context.context)
-                if many:
-                    ans.arg_types[1] = context.api.named_generic_type(
-                        "list", [TupleType(ts, context.api.named_generic_type("tuple", []))]
+                named = get_named_argument_types(stmt, context.api, context.context)
+                at: Type
+                if named is not None:
+                    at = TypedDictType(
+                        named,
+                        set(named),
+                        context.api.named_generic_type("dict", []),
                     )
                 else:
-                    ans.arg_types[1] = TupleType(
-                        ts, context.api.named_generic_type("tuple", [])
-                    )
+                    ts = get_argument_types(stmt, context.api, context.context)
+                    at = TupleType(ts, context.api.named_generic_type("tuple", []))
+                if many:
+                    ans.arg_types[1] = context.api.named_generic_type("list", [at])
+                else:
+                    ans.arg_types[1] = at
                 if note := getattr(context.api, "note"):
                     note("Use db_execute instead", context.context, code=USE_DB_EXECUTE)
             except Exception as e:
@@ -0,0 +1,381 @@
+from typing import Dict, List as _List, Optional, Tuple, Union
+
+class Dialect:
+    MariaDB: "Dialect"
+    MySQL: "Dialect"
+    def __int__(self) -> int: ...
+
+class ArgumentStyle:
+    Percent: "ArgumentStyle"
+    QuestionMark: "ArgumentStyle"
+    def __int__(self) -> int: ...
+
+class Options:
+    def __init__(
+        self,
+        *,
+        dialect: Optional[Dialect] = None,
+        argument_style: Optional[ArgumentStyle] = None,
+        warn_unquoted_identifiers: bool = False,
+        warn_none_capital_keywords: bool = False,
+        warn_unnamed_column_in_select: bool = False,
+        warn_duplicate_column_in_select: bool = False,
+        # _LIST_ expands to positional arguments, so it cannot be used with %(name)s ones
+        list_hack: bool = True,
+    ) -> None: ...
+    @property
+    def dialect(self) -> Optional[Dialect]: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+    @property
+    def warn_unquoted_identifiers(self) -> bool: ...
+    @property
+    def warn_none_capital_keywords(self) -> bool: ...
+    @property
+    def warn_unnamed_column_in_select(self) -> bool: ...
+    @property
+    def warn_duplicate_column_in_select(self) -> bool: ...
+    @property
+    def list_hack(self) -> bool: ...
+
+class AutoIncrement:
+    Yes: "AutoIncrement"
+    No: "AutoIncrement"
+    Maybe: "AutoIncrement"
+    def __int__(self) -> int: ...
+
+class Level:
+    Warning: "Level"
+    Error: "Level"
+    def __int__(self) -> int: ...
+
+class Fragment:
+    @property
+    def message(self) -> str: ...
+    @property
+    def file(self) -> str: ...
+    @property
+    def span(self) -> Tuple[int, int]: ...
+    @property
+    def line(self) -> int: ...
+    @property
+    def column(self) -> int: ...
+
+class Issue:
+    @property
+    def level(self) -> Level: ...
+    @property
+    def message(self) -> str: ...
+    @property
+    def file(self) -> str: ...
+    @property
+    def span(self) -> Tuple[int, int]: ...
+    @property
+    def line(self) -> int: ...
+    @property
+    def column(self) -> int: ...
+    @property
+    def fragments(self) -> _List[Fragment]: ...
+
+class SqlTypeError(Exception):
+    report: str
+    issues: _List[Issue]
+
+class SqlType:
+    @property
+    def kind(self) -> str: ...
+
+class Any(SqlType): ...
+
+class Integer(SqlType):
+    @property
+    def bits(self) -> Optional[int]: ...
+    @property
+    def signed(self) -> bool: ...
+
+class Float(SqlType):
+    @property
+    def bits(self) -> Optional[int]: ...
+
+class Bool(SqlType): ...
+class Bytes(SqlType): ...
+class String(SqlType): ...
+class Date(SqlType): ...
+class DateTime(SqlType): ...
+class Time(SqlType): ...
+class Timestamp(SqlType): ...
+class Json(SqlType): ...
+
+class Enum(SqlType):
+    @property
+    def values(self) -> _List[str]: ...
+
+class Set(SqlType):
+    @property
+    def values(self) -> _List[str]: ...
+
+class List(SqlType):
+    @property
+    def element(self) -> SqlType: ...
+    @property
+    def not_null(self) -> bool: ...
+
+class Argument:
+    @property
+    def key(self) -> Union[int, str]: ...
+    @property
+    def type(self) -> SqlType: ...
+    @property
+    def not_null(self) -> bool: ...
+    @property
+    def span(self) -> Optional[Tuple[int, int]]: ...
+
+class Signature:
+    @property
+    def named(self) -> bool: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def types(self) -> Dict[Union[int, str], Tuple[SqlType, bool]]: ...
+
+class Column:
+    @property
+    def name(self) -> Optional[str]: ...
+    @property
+    def type(self) -> SqlType: ...
+    @property
+    def not_null(self) -> bool: ...
+    @property
+    def table(self) -> Optional[str]: ...
+    @property
+    def column(self) -> Optional[str]: ...
+    @property
+    def span(self) -> Tuple[int, int]: ...
+    @property
+    def alias(self) -> bool: ...
+
+class Statement:
+    @property
+    def kind(self) -> str: ...
+
+class Select(Statement):
+    @property
+    def columns(self) -> _List[Column]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Delete(Statement):
+    @property
+    def tables(self) -> _List[str]: ...
+    @property
+    def has_where(self) -> bool: ...
+    @property
+    def columns(self) -> _List[Column]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Insert(Statement):
+    @property
+    def yield_autoincrement(self) -> AutoIncrement: ...
+    @property
+    def tables(self) -> _List[str]: ...
+    @property
+    def assigned_columns(self) -> _List[str]: ...
+    @property
+    def on_duplicate_key_update(self) -> bool: ...
+    @property
+    def columns(self) -> _List[Column]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Update(Statement):
+    @property
+    def tables(self) -> _List[str]: ...
+    @property
+    def assigned_columns(self) -> _List[str]: ...
+    @property
+    def has_where(self) -> bool: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Replace(Statement):
+    @property
+    def tables(self) -> _List[str]: ...
+    @property
+    def assigned_columns(self) -> _List[str]: ...
+    @property
+    def columns(self) -> _List[Column]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Invalid(Statement): ...
+
+class Create(Statement):
+    @property
+    def object_type(self) -> str: ...
+    @property
+    def names(self) -> _List[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Alter(Statement):
+    @property
+    def object_type(self) -> str: ...
+    @property
+    def names(self) -> _List[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Drop(Statement):
+    @property
+    def object_type(self) -> str: ...
+    @property
+    def names(self) -> _List[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Truncate(Statement):
+    @property
+    def tables(self) -> _List[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class SetVariables(Statement):
+    @property
+    def variables(self) -> _List[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Call(Statement):
+    @property
+    def procedure(self) -> Optional[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class LockTables(Statement):
+    @property
+    def tables(self) -> _List[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class UnlockTables(Statement):
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Transaction(Statement):
+    @property
+    def action(self) -> str: ...
+    @property
+    def savepoint(self) -> Optional[str]: ...
+    @property
+    def arguments(self) -> _List[Argument]: ...
+    @property
+    def signature(self) -> Signature: ...
+    @property
+    def argument_style(self) -> ArgumentStyle: ...
+
+class Schemas:
+    @property
+    def dialect(self) -> Dialect: ...
+    @property
+    def files(self) -> _List[str]: ...
+    def tables(self) -> _List[str]: ...
+    def columns(self, table: str) -> _List[Tuple[str, SqlType, bool]]: ...
+    def column(self, table: str, name: str) -> Tuple[SqlType, bool]: ...
+
+def parse_schemas(
+    name: str,
+    src: str,
+    *,
+    dialect: Optional[Dialect] = None,
+    options: Optional[Options] = None,
+) -> Tuple[Schemas, bool, str, _List[Issue]]: ...
+def parse_schemas_strict(
+    name: str,
+    src: str,
+    *,
+    dialect: Optional[Dialect] = None,
+    options: Optional[Options] = None,
+) -> Tuple[Schemas, _List[Issue]]: ...
+def parse_schemas_files(
+    sources: _List[Tuple[str, str]],
+    *,
+    dialect: Optional[Dialect] = None,
+    options: Optional[Options] = None,
+) -> Tuple[Schemas, bool, str, _List[Issue]]: ...
+def parse_schemas_files_strict(
+    sources: _List[Tuple[str, str]],
+    *,
+    dialect: Optional[Dialect] = None,
+    options: Optional[Options] = None,
+) -> Tuple[Schemas, _List[Issue]]: ...
+def type_statement(
+    schemas: Schemas,
+    statement: str,
+    dict_result: bool,
+    *,
+    dialect: Optional[Dialect] = None,
+    argument_style: Optional[ArgumentStyle] = None,
+    options: Optional[Options] = None,
+) -> Tuple[Statement, bool, str, _List[Issue]]: ...
+def type_statement_strict(
+    schemas: Schemas,
+    statement: str,
+    dict_result: bool,
+    *,
+    dialect: Optional[Dialect] = None,
+    argument_style: Optional[ArgumentStyle] = None,
+    options: Optional[Options] = None,
+) -> Tuple[Statement, _List[Issue]]: ...
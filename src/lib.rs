@@ -1,88 +1,746 @@
-use std::collections::HashMap;
+use std::{
+    cell::Cell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    panic::AssertUnwindSafe,
+    sync::Once,
+};
 
 use ariadne::{Label, Report, ReportKind, Source};
 use ouroboros::self_referencing;
-use pyo3::prelude::*;
-use sql_type::{Issue, SQLArguments, SQLDialect, TypeOptions};
+use pyo3::{
+    basic::CompareOp,
+    create_exception,
+    exceptions::{PyException, PyKeyError, PyValueError},
+    prelude::*,
+    types::{PyDict, PyFrozenSet, PyList, PyTuple},
+};
+use sql_type::{SQLArguments, SQLDialect, TypeOptions};
 
+mod options;
+mod placeholders;
+mod syntax;
+
+use options::Options;
+
+/// The SQL dialect used to parse schemas and statements. PostgreSQL and SQLite are left out
+/// until sql_type can parse them
+#[pyclass]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    MariaDB,
+    MySQL,
+}
+
+#[pymethods]
+impl Dialect {
+    fn __hash__(&self) -> isize {
+        *self as isize
+    }
+}
+
+impl Dialect {
+    fn name(self) -> &'static str {
+        match self {
+            Dialect::MariaDB => "MariaDB",
+            Dialect::MySQL => "MySQL",
+        }
+    }
+
+    fn sql_dialect(self) -> SQLDialect {
+        match self {
+            // sql_type only knows MariaDB, which covers the MySQL syntax we type
+            Dialect::MariaDB | Dialect::MySQL => SQLDialect::MariaDB,
+        }
+    }
+}
+
+/// The placeholder style used for arguments in statements. `$1` is left out until sql_type
+/// can parse it
+#[pyclass]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ArgumentStyle {
+    /// `%s` placeholders as used by MySQLdb
+    Percent,
+    /// `?` placeholders as used by sqlite3 and prepared cursors
+    QuestionMark,
+}
+
+#[pymethods]
+impl ArgumentStyle {
+    fn __hash__(&self) -> isize {
+        *self as isize
+    }
+}
+
+impl ArgumentStyle {
+    fn sql_arguments(self) -> SQLArguments {
+        match self {
+            ArgumentStyle::Percent => SQLArguments::Percent,
+            ArgumentStyle::QuestionMark => SQLArguments::QuestionMark,
+        }
+    }
+}
+
+/// A named file within the source text given to sql_type
+struct SourceFile {
+    name: std::string::String,
+    /// Byte range of the file within the source text
+    range: std::ops::Range<usize>,
+}
+
+/// Separates files when they are concatenated into a single source text
+const FILE_SEPARATOR: &str = "\n;\n";
+
+/// Parsed schemas, immutable once built so statements can be typed against them from
+/// several threads while the GIL is released
 #[pyclass]
 #[self_referencing]
 struct Schemas {
+    dialect: Dialect,
+    files: Vec<SourceFile>,
     src: std::string::String,
     #[borrows(src)]
     #[covariant]
     schemas: sql_type::schema::Schemas<'this>,
 }
 
-fn issue_to_report(issue: Issue) -> Report<std::ops::Range<usize>> {
+create_exception!(
+    mysql_type_plugin,
+    SqlTypeError,
+    PyException,
+    "Raised when a schema or statement has errors, with the rendered `report` and all `issues`"
+);
+
+fn sql_type_error(py: Python, report: std::string::String, issues: Vec<Issue>) -> PyErr {
+    let err = SqlTypeError::new_err(report.clone());
+    let value = err.value(py);
+    if let Err(e) = value
+        .setattr("report", report)
+        .and_then(|_| value.setattr("issues", issues.into_py(py)))
+    {
+        return e;
+    }
+    err
+}
+
+/// The severity of an issue
+#[pyclass]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Warning,
+    Error,
+}
+
+#[pymethods]
+impl Level {
+    fn __hash__(&self) -> isize {
+        *self as isize
+    }
+}
+
+/// A secondary message attached to an issue
+#[pyclass]
+#[derive(Clone)]
+struct Fragment {
+    #[pyo3(get)]
+    message: std::string::String,
+
+    /// Name of the file the fragment is in
+    #[pyo3(get)]
+    file: std::string::String,
+
+    /// Byte span of the fragment within the file as (start, end)
+    #[pyo3(get)]
+    span: (usize, usize),
+
+    /// One based line of the start of the span
+    #[pyo3(get)]
+    line: usize,
+
+    /// One based column of the start of the span
+    #[pyo3(get)]
+    column: usize,
+}
+
+const FRAGMENT_ATTRIBUTES: &[&str] = &["message", "file", "span", "line", "column"];
+
+#[pymethods]
+impl Fragment {
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, FRAGMENT_ATTRIBUTES)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, FRAGMENT_ATTRIBUTES)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, FRAGMENT_ATTRIBUTES)
+    }
+}
+
+/// A warning or error found while parsing a schema or typing a statement
+#[pyclass]
+#[derive(Clone)]
+struct Issue {
+    #[pyo3(get)]
+    level: Level,
+
+    #[pyo3(get)]
+    message: std::string::String,
+
+    /// Name of the file the issue is in
+    #[pyo3(get)]
+    file: std::string::String,
+
+    /// Byte span of the issue within the file as (start, end)
+    #[pyo3(get)]
+    span: (usize, usize),
+
+    /// One based line of the start of the span
+    #[pyo3(get)]
+    line: usize,
+
+    /// One based column of the start of the span
+    #[pyo3(get)]
+    column: usize,
+
+    #[pyo3(get)]
+    fragments: Vec<Fragment>,
+}
+
+const ISSUE_ATTRIBUTES: &[&str] = &[
+    "level",
+    "message",
+    "file",
+    "span",
+    "line",
+    "column",
+    "fragments",
+];
+
+#[pymethods]
+impl Issue {
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, ISSUE_ATTRIBUTES)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, ISSUE_ATTRIBUTES)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, ISSUE_ATTRIBUTES)
+    }
+}
+
+/// Compute the one based line and column of a byte offset in source
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// The files of a source text, used to locate and render issues
+struct Sources<'a> {
+    src: &'a str,
+    files: &'a [SourceFile],
+    sources: Vec<Source>,
+}
+
+impl<'a> Sources<'a> {
+    fn new(src: &'a str, files: &'a [SourceFile]) -> Self {
+        let sources = files
+            .iter()
+            .map(|f| Source::from(&src[f.range.clone()]))
+            .collect();
+        Sources {
+            src,
+            files,
+            sources,
+        }
+    }
+
+    fn text(&self, file: usize) -> &'a str {
+        &self.src[self.files[file].range.clone()]
+    }
+
+    /// Translate a byte span of the source text into a file and a byte span within it
+    fn locate(&self, span: &std::ops::Range<usize>) -> (usize, std::ops::Range<usize>) {
+        let file = self
+            .files
+            .iter()
+            .rposition(|f| f.range.start <= span.start)
+            .unwrap_or(0);
+        let range = &self.files[file].range;
+        let len = range.end - range.start;
+        let start = span.start.saturating_sub(range.start).min(len);
+        let end = span.end.saturating_sub(range.start).clamp(start, len);
+        (file, start..end)
+    }
+
+    /// Translate a byte span of the source text into a file and a char span as used by ariadne
+    fn char_span(&self, span: &std::ops::Range<usize>) -> (usize, std::ops::Range<usize>) {
+        let (file, span) = self.locate(span);
+        let text = self.text(file);
+        let chars = |offset: usize| {
+            text.get(..offset)
+                .map(|v| v.chars().count())
+                .unwrap_or(offset)
+        };
+        (file, chars(span.start)..chars(span.end))
+    }
+
+    fn fragment(&self, message: &str, span: &std::ops::Range<usize>) -> Fragment {
+        let (file, span) = self.locate(span);
+        let (line, column) = line_column(self.text(file), span.start);
+        Fragment {
+            message: message.to_string(),
+            file: self.files[file].name.clone(),
+            span: (span.start, span.end),
+            line,
+            column,
+        }
+    }
+}
+
+impl<'a> ariadne::Cache<usize> for &Sources<'a> {
+    fn fetch(&mut self, file: &usize) -> Result<&Source, Box<dyn std::fmt::Debug + '_>> {
+        Ok(&self.sources[*file])
+    }
+
+    fn display<'b>(&self, file: &'b usize) -> Option<Box<dyn std::fmt::Display + 'b>> {
+        Some(Box::new(self.files[*file].name.clone()))
+    }
+}
+
+fn map_issues(sources: &Sources, issues: &[sql_type::Issue]) -> Vec<Issue> {
+    issues
+        .iter()
+        .map(|issue| {
+            let Fragment {
+                file,
+                span,
+                line,
+                column,
+                ..
+            } = sources.fragment(&issue.message, &issue.span);
+            let fragments = issue
+                .fragments
+                .iter()
+                .map(|(message, span)| sources.fragment(message, span))
+                .collect();
+            Issue {
+                level: match issue.level {
+                    sql_type::Level::Warning => Level::Warning,
+                    sql_type::Level::Error => Level::Error,
+                },
+                message: issue.message.clone(),
+                file,
+                span,
+                line,
+                column,
+                fragments,
+            }
+        })
+        .collect()
+}
+
+fn issue_to_report(
+    sources: &Sources,
+    issue: &sql_type::Issue,
+) -> Report<(usize, std::ops::Range<usize>)> {
+    let span = sources.char_span(&issue.span);
     let mut builder = Report::build(
         match issue.level {
             sql_type::Level::Warning => ReportKind::Warning,
             sql_type::Level::Error => ReportKind::Error,
         },
-        (),
-        issue.span.start,
+        span.0,
+        span.1.start,
     )
     .with_config(ariadne::Config::default().with_color(false))
     .with_label(
-        Label::new(issue.span)
+        Label::new(span)
             .with_order(-1)
             .with_priority(-1)
-            .with_message(issue.message),
+            .with_message(&issue.message),
     );
-    for frag in issue.fragments {
-        builder = builder.with_label(Label::new(frag.1).with_message(frag.0));
+    for frag in &issue.fragments {
+        builder = builder.with_label(Label::new(sources.char_span(&frag.1)).with_message(&frag.0));
     }
     builder.finish()
 }
 
-struct NamedSource<'a>(&'a str, Source);
+fn issues_to_string(sources: &Sources, issues: &[sql_type::Issue]) -> (bool, std::string::String) {
+    let mut err = false;
+    let mut out = Vec::new();
+    for issue in issues {
+        if issue.level == sql_type::Level::Error {
+            err = true;
+        }
+        // ariadne panics on some spans, for instance any span in an empty file
+        let mut report = Vec::new();
+        match catch_panic(|| issue_to_report(sources, issue).write(sources, &mut report)) {
+            Ok(Ok(())) => out.append(&mut report),
+            _ => {
+                let (file, span) = sources.locate(&issue.span);
+                let (line, column) = line_column(sources.text(file), span.start);
+                out.extend_from_slice(
+                    format!(
+                        "{}:{}:{}: {}: {}\n",
+                        sources.files[file].name,
+                        line,
+                        column,
+                        match issue.level {
+                            sql_type::Level::Warning => "Warning",
+                            sql_type::Level::Error => "Error",
+                        },
+                        issue.message
+                    )
+                    .as_bytes(),
+                );
+            }
+        }
+    }
+    (err, std::string::String::from_utf8_lossy(&out).into_owned())
+}
+
+/// Describe the payload of a panic caught from sql_type
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> std::string::String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<std::string::String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+thread_local! {
+    /// True while the current thread runs code whose panics are reported as issues
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run f, returning the message of any panic instead of printing it to stderr
+///
+/// The panic hook is global and other threads may panic meanwhile, so rather than
+/// swapping it around each call it is wrapped once to stay quiet on catching threads
+fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, std::string::String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANIC.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+    let catching = CATCHING_PANIC.with(|c| c.replace(true));
+    let res = std::panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANIC.with(|c| c.set(catching));
+    res.map_err(panic_message)
+}
+
+/// Look up the named attributes of a result object
+fn attributes<'a>(
+    obj: &'a PyAny,
+    names: &[&'static str],
+) -> PyResult<Vec<(&'static str, &'a PyAny)>> {
+    names
+        .iter()
+        .map(|name| Ok((*name, obj.getattr(*name)?)))
+        .collect()
+}
+
+/// Convert lists and dicts to tuples and frozensets so a value can be hashed
+fn freeze(obj: &PyAny) -> PyResult<PyObject> {
+    let py = obj.py();
+    if let Ok(list) = obj.downcast::<PyList>() {
+        let items = list.iter().map(freeze).collect::<PyResult<Vec<_>>>()?;
+        Ok(PyTuple::new(py, items).to_object(py))
+    } else if let Ok(tuple) = obj.downcast::<PyTuple>() {
+        let items = tuple.iter().map(freeze).collect::<PyResult<Vec<_>>>()?;
+        Ok(PyTuple::new(py, items).to_object(py))
+    } else if let Ok(dict) = obj.downcast::<PyDict>() {
+        let items = dict
+            .iter()
+            .map(|(k, v)| Ok(PyTuple::new(py, [freeze(k)?, freeze(v)?]).to_object(py)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyFrozenSet::new(py, &items)?.to_object(py))
+    } else {
+        Ok(obj.to_object(py))
+    }
+}
+
+/// Render a result object as `Name(attribute=value, ...)`
+fn attributes_repr(obj: &PyAny, names: &[&'static str]) -> PyResult<std::string::String> {
+    let attributes = attributes(obj, names)?
+        .into_iter()
+        .map(|(name, value)| Ok(format!("{}={}", name, value.repr()?)))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(format!(
+        "{}({})",
+        obj.get_type().name()?,
+        attributes.join(", ")
+    ))
+}
 
-impl<'a> ariadne::Cache<()> for &NamedSource<'a> {
-    fn fetch(&mut self, _: &()) -> Result<&Source, Box<dyn std::fmt::Debug + '_>> {
-        Ok(&self.1)
+/// Compare two result objects by class and attributes
+fn attributes_richcmp(
+    obj: &PyAny,
+    other: &PyAny,
+    op: CompareOp,
+    names: &[&'static str],
+) -> PyResult<PyObject> {
+    let py = obj.py();
+    let eq = match op {
+        CompareOp::Eq => true,
+        CompareOp::Ne => false,
+        _ => return Ok(py.NotImplemented()),
+    };
+    if !other.get_type().is(obj.get_type()) {
+        return Ok((!eq).to_object(py));
+    }
+    for ((_, a), (_, b)) in attributes(obj, names)?
+        .into_iter()
+        .zip(attributes(other, names)?)
+    {
+        if !a.eq(b)? {
+            return Ok((!eq).to_object(py));
+        }
     }
+    Ok(eq.to_object(py))
+}
 
-    fn display<'b>(&self, _: &'b ()) -> Option<Box<dyn std::fmt::Display + 'b>> {
-        Some(Box::new(self.0.to_string()))
+/// Hash a result object by class and attributes
+fn attributes_hash(obj: &PyAny, names: &[&'static str]) -> PyResult<isize> {
+    let py = obj.py();
+    let mut values = vec![obj.get_type().to_object(py)];
+    for (_, value) in attributes(obj, names)? {
+        values.push(freeze(value)?);
     }
+    PyTuple::new(py, values).hash()
 }
 
-fn issues_to_string(name: &str, source: &str, issues: Vec<Issue>) -> (bool, std::string::String) {
-    let source = NamedSource(name, Source::from(source));
-    let mut err = false;
-    let mut out = Vec::new();
-    for issue in issues {
-        if issue.level == sql_type::Level::Error {
-            err = true;
+#[pymethods]
+impl Schemas {
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, &["dialect", "files"])
+    }
+
+    fn __richcmp__(&self, py: Python, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let other = match other.extract::<PyRef<Schemas>>() {
+            Ok(other) => other,
+            Err(_) => return Ok(py.NotImplemented()),
+        };
+        let eq = self.borrow_dialect() == other.borrow_dialect()
+            && self.borrow_src() == other.borrow_src()
+            && self.files() == other.files();
+        match op {
+            CompareOp::Eq => Ok(eq.to_object(py)),
+            CompareOp::Ne => Ok((!eq).to_object(py)),
+            _ => Ok(py.NotImplemented()),
         }
-        let r = issue_to_report(issue);
-        r.write(&source, &mut out).unwrap();
     }
-    (err, std::string::String::from_utf8(out).unwrap())
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (*self.borrow_dialect() as isize).hash(&mut hasher);
+        self.borrow_src().hash(&mut hasher);
+        self.files().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[getter]
+    fn dialect(&self) -> Dialect {
+        *self.borrow_dialect()
+    }
+
+    /// Names of the files the schemas were parsed from
+    #[getter]
+    fn files(&self) -> Vec<std::string::String> {
+        self.borrow_files().iter().map(|f| f.name.clone()).collect()
+    }
+
+    /// Names of all tables and views in the schemas
+    #[pyo3(text_signature = "($self)")]
+    fn tables(&self) -> Vec<std::string::String> {
+        self.borrow_schemas()
+            .schemas
+            .keys()
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    /// List of (name, type, not_null) for the columns of a table
+    #[pyo3(text_signature = "($self, table)")]
+    fn columns(
+        &self,
+        py: Python,
+        table: &str,
+    ) -> PyResult<Vec<(std::string::String, PyObject, bool)>> {
+        self.get_schema(table)?
+            .columns
+            .iter()
+            .map(|c| {
+                Ok((
+                    c.identifier.to_string(),
+                    map_type(c.type_.t.clone()).into_object(py)?,
+                    c.type_.not_null,
+                ))
+            })
+            .collect()
+    }
+
+    /// The (type, not_null) of a single column of a table
+    #[pyo3(text_signature = "($self, table, name)")]
+    fn column(&self, py: Python, table: &str, name: &str) -> PyResult<(PyObject, bool)> {
+        let column = self
+            .get_schema(table)?
+            .get_column(name)
+            .ok_or_else(|| PyKeyError::new_err(format!("No column {} in table {}", name, table)))?;
+        Ok((
+            map_type(column.type_.t.clone()).into_object(py)?,
+            column.type_.not_null,
+        ))
+    }
 }
 
-#[pyfunction]
-fn parse_schemas(name: &str, src: std::string::String) -> (Schemas, bool, std::string::String) {
-    let mut issues = Vec::new();
+impl Schemas {
+    fn get_schema(&self, table: &str) -> PyResult<&sql_type::schema::Schema<'_>> {
+        self.borrow_schemas()
+            .schemas
+            .get(table)
+            .ok_or_else(|| PyKeyError::new_err(format!("No table {}", table)))
+    }
+}
+
+#[pyfunction("*", dialect = "None", options = "None")]
+#[pyo3(text_signature = "(name, src, *, dialect=None, options=None)")]
+fn parse_schemas(
+    py: Python,
+    name: &str,
+    src: std::string::String,
+    dialect: Option<Dialect>,
+    options: Option<Options>,
+) -> PyResult<(Schemas, bool, std::string::String, Vec<Issue>)> {
+    parse_schemas_files(py, vec![(name.to_string(), src)], dialect, options)
+}
+
+/// Parse the files as a single source text separated by [FILE_SEPARATOR]
+fn build_schemas(
+    dialect: Dialect,
+    sources: Vec<(std::string::String, std::string::String)>,
+    issues: &mut Vec<sql_type::Issue>,
+    options: &TypeOptions,
+) -> Schemas {
+    let mut src = std::string::String::new();
+    let mut files = Vec::new();
+    for (name, text) in sources {
+        if !files.is_empty() {
+            src.push_str(FILE_SEPARATOR);
+        }
+        let start = src.len();
+        src.push_str(&text);
+        files.push(SourceFile {
+            name,
+            range: start..src.len(),
+        });
+    }
 
-    let schemas = SchemasBuilder {
+    SchemasBuilder {
+        dialect,
+        files,
         src,
         schemas_builder: |src: &std::string::String| {
-            sql_type::schema::parse_schemas(
-                src,
-                &mut issues,
-                &TypeOptions::new().dialect(SQLDialect::MariaDB),
-            )
+            let res = catch_panic(|| sql_type::schema::parse_schemas(src, issues, options));
+            match res {
+                Ok(schemas) => schemas,
+                Err(message) => {
+                    issues.push(sql_type::Issue::err(
+                        format!("Internal error while parsing schemas: {}", message),
+                        &(0..src.len()),
+                    ));
+                    sql_type::schema::Schemas {
+                        schemas: Default::default(),
+                        procedures: Default::default(),
+                        functions: Default::default(),
+                    }
+                }
+            }
         },
     }
-    .build();
+    .build()
+}
+
+/// Parse schemas split over a list of (name, source) files, `dialect` takes precedence over `options`
+#[pyfunction("*", dialect = "None", options = "None")]
+#[pyo3(text_signature = "(sources, *, dialect=None, options=None)")]
+fn parse_schemas_files(
+    py: Python,
+    sources: Vec<(std::string::String, std::string::String)>,
+    dialect: Option<Dialect>,
+    options: Option<Options>,
+) -> PyResult<(Schemas, bool, std::string::String, Vec<Issue>)> {
+    if sources.is_empty() {
+        return Err(PyValueError::new_err("No schema files given"));
+    }
+    let options = options.unwrap_or_default();
+    let dialect = dialect.unwrap_or_else(|| options.resolved_dialect());
+    let mut issues = Vec::new();
+    let options = Options {
+        dialect: Some(dialect),
+        ..options
+    }
+    .type_options();
+
+    // Parsing is pure Rust, so let other Python threads run meanwhile
+    let schemas = py.allow_threads(|| build_schemas(dialect, sources, &mut issues, &options));
+
+    let sources = Sources::new(schemas.borrow_src(), schemas.borrow_files());
+    let (err, messages) = issues_to_string(&sources, &issues);
+    let issues = map_issues(&sources, &issues);
+    Ok((schemas, err, messages, issues))
+}
+
+/// Like parse_schemas but raise SqlTypeError on errors, returning the warnings
+#[pyfunction("*", dialect = "None", options = "None")]
+#[pyo3(text_signature = "(name, src, *, dialect=None, options=None)")]
+fn parse_schemas_strict(
+    py: Python,
+    name: &str,
+    src: std::string::String,
+    dialect: Option<Dialect>,
+    options: Option<Options>,
+) -> PyResult<(Schemas, Vec<Issue>)> {
+    let (schemas, err, messages, issues) = parse_schemas(py, name, src, dialect, options)?;
+    if err {
+        return Err(sql_type_error(py, messages, issues));
+    }
+    Ok((schemas, issues))
+}
 
-    let (err, messages) = issues_to_string(name, schemas.borrow_src(), issues);
-    (schemas, err, messages)
+/// Like parse_schemas_files but raise SqlTypeError on errors, returning the warnings
+#[pyfunction("*", dialect = "None", options = "None")]
+#[pyo3(text_signature = "(sources, *, dialect=None, options=None)")]
+fn parse_schemas_files_strict(
+    py: Python,
+    sources: Vec<(std::string::String, std::string::String)>,
+    dialect: Option<Dialect>,
+    options: Option<Options>,
+) -> PyResult<(Schemas, Vec<Issue>)> {
+    let (schemas, err, messages, issues) = parse_schemas_files(py, sources, dialect, options)?;
+    if err {
+        return Err(sql_type_error(py, messages, issues));
+    }
+    Ok((schemas, issues))
 }
 
-#[derive(Clone, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 enum ArgumentKey {
     Identifier(std::string::String),
     Index(usize),
@@ -97,249 +755,1940 @@ impl IntoPy<PyObject> for ArgumentKey {
     }
 }
 
+/// An argument of a statement, `key` is the index for positional arguments
 #[pyclass]
-struct Any {}
+#[derive(Clone)]
+struct Argument {
+    #[pyo3(get)]
+    key: ArgumentKey,
 
-#[pyclass]
-struct Integer {}
+    type_: PyObject,
 
-#[pyclass]
-struct Float {}
+    #[pyo3(get)]
+    not_null: bool,
 
-#[pyclass]
-struct Bool {}
+    /// Byte span of the placeholder within the statement as (start, end)
+    #[pyo3(get)]
+    span: Option<(usize, usize)>,
+}
 
-#[pyclass]
-struct Bytes {}
+const ARGUMENT_ATTRIBUTES: &[&str] = &["key", "type", "not_null", "span"];
 
-#[pyclass]
-struct String {}
+#[pymethods]
+impl Argument {
+    #[getter]
+    fn r#type(&self) -> PyObject {
+        self.type_.clone()
+    }
 
-#[pyclass]
-struct Enum {
-    #[pyo3(get)]
-    values: Vec<std::string::String>,
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, ARGUMENT_ATTRIBUTES)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, ARGUMENT_ATTRIBUTES)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, ARGUMENT_ATTRIBUTES)
+    }
 }
 
+/// The parameters of a statement, `arguments` has one entry per distinct key in order of first use
+#[pyclass]
 #[derive(Clone)]
-enum Type {
-    Any,
-    Integer,
-    Float,
-    Bool,
-    Bytes,
-    String,
-    Enum(Vec<std::string::String>),
+struct Signature {
+    /// True if the statement uses `%(name)s` arguments
+    #[pyo3(get)]
+    named: bool,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
 }
 
-impl IntoPy<PyObject> for Type {
-    fn into_py(self, py: Python) -> PyObject {
-        match self {
-            Type::Any => Py::new(py, Any {}).unwrap().to_object(py),
-            Type::Integer => Py::new(py, Integer {}).unwrap().to_object(py),
-            Type::Float => Py::new(py, Float {}).unwrap().to_object(py),
-            Type::Bool => Py::new(py, Bool {}).unwrap().to_object(py),
-            Type::Bytes => Py::new(py, Bytes {}).unwrap().to_object(py),
-            Type::String => Py::new(py, String {}).unwrap().to_object(py),
-            Type::Enum(values) => Py::new(py, Enum { values }).unwrap().to_object(py),
+const SIGNATURE_ATTRIBUTES: &[&str] = &["named", "arguments"];
+
+#[pymethods]
+impl Signature {
+    /// Mapping of keys to (type, not_null), suitable for building a TypedDict of named parameters
+    #[getter]
+    fn types(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        for a in &self.arguments {
+            dict.set_item(a.key.clone().into_py(py), (a.type_.clone(), a.not_null))?;
         }
+        Ok(dict.to_object(py))
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, SIGNATURE_ATTRIBUTES)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, SIGNATURE_ATTRIBUTES)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, SIGNATURE_ATTRIBUTES)
     }
 }
 
+/// A column of a select result
 #[pyclass]
-struct Select {
+#[derive(Clone)]
+struct Column {
     #[pyo3(get)]
-    columns: Vec<(Option<std::string::String>, Type, bool)>,
+    name: Option<std::string::String>,
 
-    #[pyo3(get)]
-    arguments: HashMap<ArgumentKey, (Type, bool)>,
-}
+    type_: PyObject,
 
-#[pyclass]
-struct Delete {
     #[pyo3(get)]
-    arguments: HashMap<ArgumentKey, (Type, bool)>,
-}
+    not_null: bool,
 
-#[pyclass]
-struct Insert {
+    /// Table of the schema when the expression is a plain column reference
     #[pyo3(get)]
-    yield_autoincrement: &'static str,
+    table: Option<std::string::String>,
 
+    /// Column of the schema when the expression is a plain column reference
     #[pyo3(get)]
-    arguments: HashMap<ArgumentKey, (Type, bool)>,
-}
+    column: Option<std::string::String>,
 
-#[pyclass]
-struct Update {
+    /// Byte span of the select expression within the statement as (start, end)
     #[pyo3(get)]
-    arguments: HashMap<ArgumentKey, (Type, bool)>,
-}
+    span: (usize, usize),
 
-#[pyclass]
-struct Replace {
+    /// True if the name was given with AS
     #[pyo3(get)]
-    arguments: HashMap<ArgumentKey, (Type, bool)>,
+    alias: bool,
 }
 
-#[pyclass]
+const COLUMN_ATTRIBUTES: &[&str] = &[
+    "name", "type", "not_null", "table", "column", "span", "alias",
+];
+
+#[pymethods]
+impl Column {
+    #[getter]
+    fn r#type(&self) -> PyObject {
+        self.type_.clone()
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, COLUMN_ATTRIBUTES)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, COLUMN_ATTRIBUTES)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, COLUMN_ATTRIBUTES)
+    }
+}
+
+/// Base class of all types, `kind` is the lower case name of the type
+#[pyclass(subclass)]
+struct SqlType {
+    #[pyo3(get)]
+    kind: &'static str,
+
+    /// Attributes of the subclass used for repr, equality and hashing
+    attributes: &'static [&'static str],
+}
+
+#[pymethods]
+impl SqlType {
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, slf.borrow().attributes)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, slf.borrow().attributes)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, slf.borrow().attributes)
+    }
+}
+
+#[pyclass(extends=SqlType)]
+struct Any {}
+
+#[pyclass(extends=SqlType)]
+struct Integer {
+    /// Width of the integer in bits, or None if the width is not known
+    #[pyo3(get)]
+    bits: Option<u8>,
+
+    #[pyo3(get)]
+    signed: bool,
+}
+
+#[pyclass(extends=SqlType)]
+struct Float {
+    /// Width of the float in bits, or None if the width is not known
+    #[pyo3(get)]
+    bits: Option<u8>,
+}
+
+#[pyclass(extends=SqlType)]
+struct Bool {}
+
+#[pyclass(extends=SqlType)]
+struct Bytes {}
+
+#[pyclass(extends=SqlType)]
+struct String {}
+
+#[pyclass(extends=SqlType)]
+struct Date {}
+
+#[pyclass(extends=SqlType)]
+struct DateTime {}
+
+#[pyclass(extends=SqlType)]
+struct Time {}
+
+#[pyclass(extends=SqlType)]
+struct Timestamp {}
+
+#[pyclass(extends=SqlType)]
+struct Json {}
+
+#[pyclass(extends=SqlType)]
+struct Enum {
+    #[pyo3(get)]
+    values: Vec<std::string::String>,
+}
+
+#[pyclass(extends=SqlType)]
+struct Set {
+    #[pyo3(get)]
+    values: Vec<std::string::String>,
+}
+
+/// The type of a `_LIST_` argument, a sequence of `element`
+#[pyclass(extends=SqlType)]
+struct List {
+    #[pyo3(get)]
+    element: PyObject,
+
+    /// True if the elements may not be None
+    #[pyo3(get)]
+    not_null: bool,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Type {
+    Any,
+    Integer { bits: Option<u8>, signed: bool },
+    Float { bits: Option<u8> },
+    Bool,
+    Bytes,
+    String,
+    Date,
+    DateTime,
+    Time,
+    Timestamp,
+    Json,
+    Enum(Vec<std::string::String>),
+    Set(Vec<std::string::String>),
+    List { element: Box<Type>, not_null: bool },
+}
+
+impl Type {
+    fn kind(&self) -> &'static str {
+        match self {
+            Type::Any => "any",
+            Type::Integer { .. } => "integer",
+            Type::Float { .. } => "float",
+            Type::Bool => "bool",
+            Type::Bytes => "bytes",
+            Type::String => "string",
+            Type::Date => "date",
+            Type::DateTime => "datetime",
+            Type::Time => "time",
+            Type::Timestamp => "timestamp",
+            Type::Json => "json",
+            Type::Enum(_) => "enum",
+            Type::Set(_) => "set",
+            Type::List { .. } => "list",
+        }
+    }
+
+    /// A short description used in messages, including the width of numbers
+    fn describe(&self) -> std::string::String {
+        match self {
+            Type::Integer {
+                bits: Some(bits),
+                signed,
+            } => format!("{}int{}", if *signed { "" } else { "u" }, bits),
+            Type::Float { bits: Some(bits) } => format!("float{}", bits),
+            Type::Enum(values) | Type::Set(values) => format!(
+                "{}({})",
+                self.kind(),
+                values
+                    .iter()
+                    .map(|v| format!("'{}'", v))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            Type::List { element, .. } => format!("list of {}", element.describe()),
+            _ => self.kind().to_string(),
+        }
+    }
+
+    fn attributes(&self) -> &'static [&'static str] {
+        match self {
+            Type::Integer { .. } => &["bits", "signed"],
+            Type::Float { .. } => &["bits"],
+            Type::Enum(_) | Type::Set(_) => &["values"],
+            Type::List { .. } => &["element", "not_null"],
+            _ => &[],
+        }
+    }
+
+    fn into_object(self, py: Python) -> PyResult<PyObject> {
+        let base = SqlType {
+            kind: self.kind(),
+            attributes: self.attributes(),
+        };
+        Ok(match self {
+            Type::Any => Py::new(py, (Any {}, base))?.to_object(py),
+            Type::Integer { bits, signed } => {
+                Py::new(py, (Integer { bits, signed }, base))?.to_object(py)
+            }
+            Type::Float { bits } => Py::new(py, (Float { bits }, base))?.to_object(py),
+            Type::Bool => Py::new(py, (Bool {}, base))?.to_object(py),
+            Type::Bytes => Py::new(py, (Bytes {}, base))?.to_object(py),
+            Type::String => Py::new(py, (String {}, base))?.to_object(py),
+            Type::Date => Py::new(py, (Date {}, base))?.to_object(py),
+            Type::DateTime => Py::new(py, (DateTime {}, base))?.to_object(py),
+            Type::Time => Py::new(py, (Time {}, base))?.to_object(py),
+            Type::Timestamp => Py::new(py, (Timestamp {}, base))?.to_object(py),
+            Type::Json => Py::new(py, (Json {}, base))?.to_object(py),
+            Type::Enum(values) => Py::new(py, (Enum { values }, base))?.to_object(py),
+            Type::Set(values) => Py::new(py, (Set { values }, base))?.to_object(py),
+            Type::List { element, not_null } => {
+                let element = element.into_object(py)?;
+                Py::new(py, (List { element, not_null }, base))?.to_object(py)
+            }
+        })
+    }
+}
+
+/// Base class of all typed statements, `kind` is the lower case name of the statement
+#[pyclass(subclass)]
+struct Statement {
+    #[pyo3(get)]
+    kind: &'static str,
+
+    /// Attributes of the subclass used for repr, equality and hashing
+    attributes: &'static [&'static str],
+}
+
+#[pymethods]
+impl Statement {
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, slf.borrow().attributes)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, slf.borrow().attributes)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, slf.borrow().attributes)
+    }
+}
+
+#[pyclass(extends=Statement)]
+struct Select {
+    #[pyo3(get)]
+    columns: Vec<Column>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
+struct Delete {
+    /// Tables rows are deleted from
+    #[pyo3(get)]
+    tables: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    has_where: bool,
+
+    /// Columns of the RETURNING clause
+    #[pyo3(get)]
+    columns: Vec<Column>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+/// Whether an insert yields an auto increment id, Maybe for INSERT IGNORE and ON DUPLICATE KEY UPDATE
+#[pyclass]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AutoIncrement {
+    Yes,
+    No,
+    Maybe,
+}
+
+impl From<sql_type::AutoIncrementId> for AutoIncrement {
+    fn from(v: sql_type::AutoIncrementId) -> Self {
+        match v {
+            sql_type::AutoIncrementId::Yes => AutoIncrement::Yes,
+            sql_type::AutoIncrementId::No => AutoIncrement::No,
+            sql_type::AutoIncrementId::Optional => AutoIncrement::Maybe,
+        }
+    }
+}
+
+#[pymethods]
+impl AutoIncrement {
+    fn __hash__(&self) -> isize {
+        *self as isize
+    }
+}
+
+#[pyclass(extends=Statement)]
+struct Insert {
+    #[pyo3(get)]
+    yield_autoincrement: AutoIncrement,
+
+    /// Table inserted into
+    #[pyo3(get)]
+    tables: Vec<std::string::String>,
+
+    /// Columns given values by the insert
+    #[pyo3(get)]
+    assigned_columns: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    on_duplicate_key_update: bool,
+
+    /// Columns of the RETURNING clause
+    #[pyo3(get)]
+    columns: Vec<Column>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
+struct Update {
+    /// Tables with columns assigned by the update
+    #[pyo3(get)]
+    tables: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    assigned_columns: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    has_where: bool,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
+struct Replace {
+    /// Table replaced into
+    #[pyo3(get)]
+    tables: Vec<std::string::String>,
+
+    /// Columns given values by the replace
+    #[pyo3(get)]
+    assigned_columns: Vec<std::string::String>,
+
+    /// Columns of the RETURNING clause
+    #[pyo3(get)]
+    columns: Vec<Column>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
 struct Invalid {}
 
+/// CREATE of a table, view, trigger or function
+#[pyclass(extends=Statement)]
+struct Create {
+    #[pyo3(get)]
+    object_type: &'static str,
+
+    #[pyo3(get)]
+    names: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+/// ALTER TABLE
+#[pyclass(extends=Statement)]
+struct Alter {
+    #[pyo3(get)]
+    object_type: &'static str,
+
+    #[pyo3(get)]
+    names: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+/// DROP of a table, view, database, event, function, procedure, server or trigger
+#[pyclass(extends=Statement)]
+struct Drop {
+    #[pyo3(get)]
+    object_type: &'static str,
+
+    #[pyo3(get)]
+    names: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
+struct Truncate {
+    #[pyo3(get)]
+    tables: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+/// SET of session variables
+#[pyclass(extends=Statement)]
+struct SetVariables {
+    #[pyo3(get)]
+    variables: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+/// CALL of a stored procedure, whose arguments cannot be typed
+#[pyclass(extends=Statement)]
+struct Call {
+    #[pyo3(get)]
+    procedure: Option<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
+struct LockTables {
+    #[pyo3(get)]
+    tables: Vec<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+#[pyclass(extends=Statement)]
+struct UnlockTables {
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
+/// Transaction control, `action` is one of start, commit, rollback, savepoint and release
+#[pyclass(extends=Statement)]
+struct Transaction {
+    #[pyo3(get)]
+    action: &'static str,
+
+    /// The savepoint of SAVEPOINT, RELEASE SAVEPOINT and ROLLBACK TO
+    #[pyo3(get)]
+    savepoint: Option<std::string::String>,
+
+    #[pyo3(get)]
+    arguments: Vec<Argument>,
+
+    #[pyo3(get)]
+    signature: Signature,
+
+    #[pyo3(get)]
+    argument_style: ArgumentStyle,
+}
+
 fn map_type(t: sql_type::Type<'_>) -> Type {
     match t {
         sql_type::Type::Args(_, _) => Type::Any,
-        sql_type::Type::Base(v) => {
-            match v {
-                sql_type::BaseType::Any => Type::Any,
-                sql_type::BaseType::Bool => Type::Bool,
-                sql_type::BaseType::Bytes => Type::Bytes,
-                sql_type::BaseType::Date => Type::Any, //TODO
-                sql_type::BaseType::DateTime => Type::Any, //TODO
-                sql_type::BaseType::Float => Type::Float,
-                sql_type::BaseType::Integer => Type::Integer,
-                sql_type::BaseType::String => Type::String,
-                sql_type::BaseType::Time => Type::Any, //TODO
-                sql_type::BaseType::TimeStamp => Type::Any, //TODO
-            }
-        }
+        sql_type::Type::Base(v) => match v {
+            sql_type::BaseType::Any => Type::Any,
+            sql_type::BaseType::Bool => Type::Bool,
+            sql_type::BaseType::Bytes => Type::Bytes,
+            sql_type::BaseType::Date => Type::Date,
+            sql_type::BaseType::DateTime => Type::DateTime,
+            sql_type::BaseType::Float => Type::Float { bits: None },
+            sql_type::BaseType::Integer => Type::Integer {
+                bits: None,
+                signed: true,
+            },
+            sql_type::BaseType::String => Type::String,
+            sql_type::BaseType::Time => Type::Time,
+            sql_type::BaseType::TimeStamp => Type::Timestamp,
+        },
         sql_type::Type::Enum(v) => Type::Enum(v.iter().map(|v| v.to_string()).collect()),
-        sql_type::Type::F32 => Type::Float,
-        sql_type::Type::F64 => Type::Float,
-        sql_type::Type::I16 => Type::Integer,
-        sql_type::Type::I32 => Type::Integer,
-        sql_type::Type::I64 => Type::Integer,
-        sql_type::Type::I8 => Type::Integer,
+        sql_type::Type::F32 => Type::Float { bits: Some(32) },
+        sql_type::Type::F64 => Type::Float { bits: Some(64) },
+        sql_type::Type::I16 => Type::Integer {
+            bits: Some(16),
+            signed: true,
+        },
+        sql_type::Type::I32 => Type::Integer {
+            bits: Some(32),
+            signed: true,
+        },
+        sql_type::Type::I64 => Type::Integer {
+            bits: Some(64),
+            signed: true,
+        },
+        sql_type::Type::I8 => Type::Integer {
+            bits: Some(8),
+            signed: true,
+        },
         sql_type::Type::Invalid => Type::Any,
-        sql_type::Type::JSON => Type::Any,
-        sql_type::Type::Set(_) => Type::String,
-        sql_type::Type::U16 => Type::Integer,
-        sql_type::Type::U32 => Type::Integer,
-        sql_type::Type::U64 => Type::Integer,
-        sql_type::Type::U8 => Type::Integer,
+        sql_type::Type::JSON => Type::Json,
+        sql_type::Type::Set(v) => Type::Set(v.iter().map(|v| v.to_string()).collect()),
+        sql_type::Type::U16 => Type::Integer {
+            bits: Some(16),
+            signed: false,
+        },
+        sql_type::Type::U32 => Type::Integer {
+            bits: Some(32),
+            signed: false,
+        },
+        sql_type::Type::U64 => Type::Integer {
+            bits: Some(64),
+            signed: false,
+        },
+        sql_type::Type::U8 => Type::Integer {
+            bits: Some(8),
+            signed: false,
+        },
         sql_type::Type::Null => Type::Any,
     }
 }
 
+/// An argument before it is converted to a Python object
+#[derive(Clone)]
+struct TypedArgument {
+    key: ArgumentKey,
+    type_: Type,
+    not_null: bool,
+    span: Option<std::ops::Range<usize>>,
+}
+
+impl TypedArgument {
+    fn into_argument(self, py: Python) -> PyResult<Argument> {
+        Ok(Argument {
+            key: self.key,
+            type_: self.type_.into_object(py)?,
+            not_null: self.not_null,
+            span: self.span.map(|s| (s.start, s.end)),
+        })
+    }
+}
+
+/// Order the arguments by their placeholders in statement, padding unconstrained ones with Any
+fn order_arguments(
+    placeholders: &[placeholders::Placeholder],
+    arguments: Vec<(sql_type::ArgumentKey<'_>, sql_type::FullType<'_>)>,
+) -> Vec<TypedArgument> {
+    let mut positional = HashMap::new();
+    let mut named = Vec::new();
+    for (k, v) in arguments {
+        match k {
+            sql_type::ArgumentKey::Index(i) => {
+                positional.insert(i, v);
+            }
+            sql_type::ArgumentKey::Identifier(i) => named.push((i.to_string(), v)),
+        }
+    }
+    let count = positional
+        .keys()
+        .map(|i| i + 1)
+        .max()
+        .unwrap_or(0)
+        .max(placeholders.len());
+
+    let mut res = Vec::with_capacity(count + named.len());
+    for i in 0..count {
+        let (type_, not_null) = match positional.remove(&i) {
+            Some(v) => (map_type(v.t), v.not_null),
+            None => (Type::Any, false),
+        };
+        let placeholder = placeholders.get(i);
+        let (type_, not_null) = match placeholder {
+            Some(p) if p.list => (
+                Type::List {
+                    element: Box::new(type_),
+                    not_null,
+                },
+                true,
+            ),
+            _ => (type_, not_null),
+        };
+        res.push(TypedArgument {
+            key: match placeholder.and_then(|p| p.name) {
+                Some(name) => ArgumentKey::Identifier(name.to_string()),
+                None => ArgumentKey::Index(i),
+            },
+            type_,
+            not_null,
+            span: placeholder.map(|p| p.span.clone()),
+        });
+    }
+    for (name, v) in named {
+        res.push(TypedArgument {
+            key: ArgumentKey::Identifier(name),
+            type_: map_type(v.t),
+            not_null: v.not_null,
+            span: None,
+        });
+    }
+    res
+}
+
+/// Merge arguments sharing a key, a named argument used twice must satisfy both uses
+/// so it is an error if they are typed differently
+fn merge_arguments(
+    arguments: &[TypedArgument],
+    issues: &mut Vec<sql_type::Issue>,
+) -> Vec<TypedArgument> {
+    let mut merged: Vec<TypedArgument> = Vec::new();
+    // The span of the use each merged type comes from
+    let mut typed_at = Vec::new();
+    for argument in arguments {
+        let i = match merged.iter().position(|a| a.key == argument.key) {
+            Some(i) => i,
+            None => {
+                merged.push(argument.clone());
+                typed_at.push(argument.span.clone());
+                continue;
+            }
+        };
+        let m = &mut merged[i];
+        m.not_null |= argument.not_null;
+        if m.type_ == Type::Any {
+            m.type_ = argument.type_.clone();
+            typed_at[i] = argument.span.clone();
+        } else if argument.type_ != Type::Any && argument.type_ != m.type_ {
+            let name = match &argument.key {
+                ArgumentKey::Identifier(name) => name.clone(),
+                ArgumentKey::Index(i) => i.to_string(),
+            };
+            let span = argument.span.clone().unwrap_or_default();
+            let mut issue = sql_type::Issue::err(
+                format!(
+                    "Argument {} is used as both {} and {}",
+                    name,
+                    m.type_.describe(),
+                    argument.type_.describe()
+                ),
+                &span,
+            );
+            if let Some(first) = &typed_at[i] {
+                issue = issue.frag(format!("Used as {} here", m.type_.describe()), first);
+            }
+            issues.push(issue);
+        }
+    }
+    merged
+}
+
+/// Map the arguments to Python objects in order of their placeholders, and the signature
+/// with one entry per distinct key
 fn map_arguments(
+    py: Python,
+    placeholders: &[placeholders::Placeholder],
     arguments: Vec<(sql_type::ArgumentKey<'_>, sql_type::FullType<'_>)>,
-) -> HashMap<ArgumentKey, (Type, bool)> {
+    issues: &mut Vec<sql_type::Issue>,
+) -> PyResult<(Vec<Argument>, Signature)> {
+    let arguments = order_arguments(placeholders, arguments);
+    let merged = merge_arguments(&arguments, issues);
+    let signature = Signature {
+        named: merged
+            .iter()
+            .any(|a| matches!(a.key, ArgumentKey::Identifier(_))),
+        arguments: merged
+            .into_iter()
+            .map(|a| a.into_argument(py))
+            .collect::<PyResult<_>>()?,
+    };
+    let arguments = arguments
+        .into_iter()
+        .map(|a| a.into_argument(py))
+        .collect::<PyResult<_>>()?;
+    Ok((arguments, signature))
+}
+
+/// Map the columns of a select, with their provenance when the parsed select is given
+fn map_columns(
+    py: Python,
+    schemas: &Schemas,
+    parsed: Option<&sql_parse::Statement>,
+    columns: Vec<sql_type::SelectTypeColumn>,
+) -> PyResult<Vec<Column>> {
+    let sources = parsed
+        .and_then(|s| syntax::select_sources(schemas.borrow_schemas(), s))
+        .filter(|s| s.len() == columns.len());
+    columns
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let source = sources.as_ref().map(|s| &s[i]);
+            let span = source.map_or(v.span, |s| s.span.clone());
+            Ok(Column {
+                name: v.name.map(|v| v.to_string()),
+                type_: map_type(v.type_.t).into_object(py)?,
+                not_null: v.type_.not_null,
+                table: source.and_then(|s| s.table.clone()),
+                column: source.and_then(|s| s.column.clone()),
+                span: (span.start, span.end),
+                alias: source.is_some_and(|s| s.alias),
+            })
+        })
+        .collect()
+}
+
+/// Map a statement sql_type cannot type to its statement class
+fn map_other(
+    py: Python,
+    other: syntax::Other,
+    arguments: Vec<Argument>,
+    signature: Signature,
+    argument_style: ArgumentStyle,
+) -> PyResult<PyObject> {
+    let object_type = other.object;
+    let mut names: Vec<std::string::String> =
+        other.names.into_iter().map(|(name, _)| name).collect();
+    Ok(match other.kind {
+        syntax::OtherKind::Create => Py::new(
+            py,
+            (
+                Create {
+                    object_type,
+                    names,
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "create",
+                    attributes: &[
+                        "object_type",
+                        "names",
+                        "arguments",
+                        "signature",
+                        "argument_style",
+                    ],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::Alter => Py::new(
+            py,
+            (
+                Alter {
+                    object_type,
+                    names,
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "alter",
+                    attributes: &[
+                        "object_type",
+                        "names",
+                        "arguments",
+                        "signature",
+                        "argument_style",
+                    ],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::Drop => Py::new(
+            py,
+            (
+                Drop {
+                    object_type,
+                    names,
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "drop",
+                    attributes: &[
+                        "object_type",
+                        "names",
+                        "arguments",
+                        "signature",
+                        "argument_style",
+                    ],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::Truncate => Py::new(
+            py,
+            (
+                Truncate {
+                    tables: names,
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "truncate",
+                    attributes: &["tables", "arguments", "signature", "argument_style"],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::SetVariables => Py::new(
+            py,
+            (
+                SetVariables {
+                    variables: names,
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "set_variables",
+                    attributes: &["variables", "arguments", "signature", "argument_style"],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::Call => Py::new(
+            py,
+            (
+                Call {
+                    procedure: names.pop(),
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "call",
+                    attributes: &["procedure", "arguments", "signature", "argument_style"],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::LockTables => Py::new(
+            py,
+            (
+                LockTables {
+                    tables: names,
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "lock_tables",
+                    attributes: &["tables", "arguments", "signature", "argument_style"],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::UnlockTables => Py::new(
+            py,
+            (
+                UnlockTables {
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "unlock_tables",
+                    attributes: &["arguments", "signature", "argument_style"],
+                },
+            ),
+        )?
+        .to_object(py),
+        syntax::OtherKind::Transaction => Py::new(
+            py,
+            (
+                Transaction {
+                    action: object_type,
+                    savepoint: names.pop(),
+                    arguments,
+                    signature,
+                    argument_style,
+                },
+                Statement {
+                    kind: "transaction",
+                    attributes: &[
+                        "action",
+                        "savepoint",
+                        "arguments",
+                        "signature",
+                        "argument_style",
+                    ],
+                },
+            ),
+        )?
+        .to_object(py),
+    })
+}
+
+/// Parse src with sql_parse, None if it fails or panics
+fn parse_statement<'a>(
+    src: &'a str,
+    issues: &mut Vec<sql_type::Issue>,
+    options: &sql_parse::ParseOptions,
+) -> Option<sql_parse::Statement<'a>> {
+    catch_panic(|| sql_parse::parse_statement(src, issues, options))
+        .ok()
+        .flatten()
+}
+
+/// The number of placeholders before a byte offset in the statement
+fn placeholders_before(placeholders: &[placeholders::Placeholder], offset: usize) -> usize {
+    placeholders
+        .iter()
+        .filter(|p| p.span.start < offset)
+        .count()
+}
+
+/// Offset preserving select of the RETURNING clause at returning in src from table,
+/// which may be qualified with its database
+fn returning_select(
+    src: &str,
+    returning: &std::ops::Range<usize>,
+    table: &str,
+) -> std::string::String {
+    let table = table
+        .split('.')
+        .map(|p| format!("`{}`", p))
+        .collect::<Vec<_>>()
+        .join(".");
+    format!(
+        "{}SELECT   {} FROM {}",
+        " ".repeat(returning.start),
+        &src[returning.start + "RETURNING".len()..returning.end],
+        table
+    )
+}
+
+/// Number arguments typed in a part of a statement after the `offset` placeholders before it
+fn shift_arguments<'a>(
+    arguments: Vec<(sql_type::ArgumentKey<'a>, sql_type::FullType<'a>)>,
+    offset: usize,
+) -> Vec<(sql_type::ArgumentKey<'a>, sql_type::FullType<'a>)> {
     arguments
         .into_iter()
-        .map(|(k, v)| {
-            let k = match k {
-                sql_type::ArgumentKey::Index(i) => ArgumentKey::Index(i),
-                sql_type::ArgumentKey::Identifier(i) => ArgumentKey::Identifier(i.to_string()),
-            };
-            (k, (map_type(v.t), v.not_null))
+        .map(|(k, v)| match k {
+            sql_type::ArgumentKey::Index(i) => (sql_type::ArgumentKey::Index(i + offset), v),
+            k => (k, v),
         })
         .collect()
 }
 
-#[pyfunction]
+/// Run sql_type on src, turning a panic into an error on statement and None
+fn catch_type_statement<'a>(
+    schemas: &'a sql_type::schema::Schemas<'a>,
+    statement: &str,
+    src: &'a str,
+    issues: &mut Vec<sql_type::Issue>,
+    options: &TypeOptions,
+) -> Option<sql_type::StatementType<'a>> {
+    match catch_panic(|| sql_type::type_statement(schemas, src, issues, options)) {
+        Ok(stmt) => Some(stmt),
+        Err(message) => {
+            issues.push(sql_type::Issue::err(
+                format!("Internal error while typing {:?}: {}", statement, message),
+                &(0..statement.len()),
+            ));
+            None
+        }
+    }
+}
+
+/// Type a statement against schemas, `dialect` and `argument_style` take precedence over
+/// `options` and `dict_result` enables the warnings for unnamed and duplicate columns
+#[pyfunction("*", dialect = "None", argument_style = "None", options = "None")]
+#[pyo3(
+    text_signature = "(schemas, statement, dict_result, *, dialect=None, argument_style=None, options=None)"
+)]
 fn type_statement(
     py: Python,
     schemas: &Schemas,
     statement: &str,
     dict_result: bool,
-) -> PyResult<(PyObject, bool, std::string::String)> {
+    dialect: Option<Dialect>,
+    argument_style: Option<ArgumentStyle>,
+    options: Option<Options>,
+) -> PyResult<(PyObject, bool, std::string::String, Vec<Issue>)> {
     let mut issues = Vec::new();
 
-    let mut options = TypeOptions::new()
-        .dialect(SQLDialect::MariaDB)
-        .arguments(SQLArguments::Percent);
+    let options = options.unwrap_or_default();
+    let argument_style = argument_style.unwrap_or(options.argument_style);
+    let schemas_dialect = *schemas.borrow_dialect();
+    if let Some(dialect) = dialect.or(options.dialect) {
+        if dialect != schemas_dialect {
+            return Err(PyValueError::new_err(format!(
+                "Cannot type a {} statement against schemas parsed as {}",
+                dialect.name(),
+                schemas_dialect.name()
+            )));
+        }
+    }
+
+    let options = Options {
+        dialect: Some(schemas_dialect),
+        argument_style,
+        warn_unnamed_column_in_select: options.warn_unnamed_column_in_select || dict_result,
+        warn_duplicate_column_in_select: options.warn_duplicate_column_in_select || dict_result,
+        ..options
+    };
+    let type_options = options
+        .type_options()
+        .arguments(argument_style.sql_arguments());
+    let parse_options = options.parse_options();
 
-    if dict_result {
-        options = options
-            .warn_duplicate_column_in_select(true)
-            .warn_unnamed_column_in_select(true);
+    let placeholders = placeholders::placeholders(statement, argument_style, options.list_hack);
+    let first_positional = placeholders.iter().find(|p| p.name.is_none());
+    let first_named = placeholders.iter().find(|p| p.name.is_some());
+    if let (Some(a), Some(b)) = (first_positional, first_named) {
+        let (first, second) = if a.span.start < b.span.start {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let issue = if a.list {
+            sql_type::Issue::err("_LIST_ cannot be used with named arguments", &a.span)
+                .frag("Named argument here", &b.span)
+        } else {
+            sql_type::Issue::err("Cannot mix positional and named arguments", &second.span)
+                .frag("First argument here", &first.span)
+        };
+        issues.push(issue);
     }
+    let src = placeholders::rewrite(statement, argument_style, &placeholders);
+
+    // sql_parse cannot parse RETURNING, so the clause is blanked out here and
+    // typed below as a select from the target table
+    let returning = placeholders::returning(&src);
+    let dml_src = match &returning {
+        Some(r) => {
+            let mut dml_src = src.clone();
+            dml_src.replace_range(r.clone(), &" ".repeat(r.len()));
+            dml_src
+        }
+        None => src.clone(),
+    };
 
-    let stmt = sql_type::type_statement(schemas.borrow_schemas(), statement, &mut issues, &options);
+    let typed_from = issues.len();
+    // Typing is pure Rust, so let other Python threads run meanwhile. sql_type does not
+    // report which tables and columns are involved, so they are read from the syntax tree
+    let (stmt, parsed, mut parse_issues, targets, mut other) = py.allow_threads(|| {
+        let stmt = catch_type_statement(
+            schemas.borrow_schemas(),
+            statement,
+            &dml_src,
+            &mut issues,
+            &type_options,
+        );
+        let mut parse_issues = Vec::new();
+        // sql_type panicked while parsing, so sql_parse would panic the same way
+        let parsed = match stmt {
+            Some(_) => parse_statement(&dml_src, &mut parse_issues, &parse_options),
+            None => None,
+        };
+        let targets = parsed
+            .as_ref()
+            .and_then(|parsed| syntax::targets(schemas.borrow_schemas(), parsed))
+            .unwrap_or_default();
+        let other = match stmt {
+            Some(sql_type::StatementType::Invalid) => syntax::other(parsed.as_ref(), &dml_src),
+            _ => None,
+        };
+        (stmt, parsed, parse_issues, targets, other)
+    });
+
+    let stmt = stmt.unwrap_or(sql_type::StatementType::Invalid);
+    if let Some(other) = &mut other {
+        // sql_type only adds that it cannot type the statement to the issues of parsing it,
+        // and those are meaningless for the statements sql_parse cannot parse at all
+        issues.truncate(typed_from);
+        if parsed.is_some() {
+            issues.append(&mut parse_issues);
+        }
+        if other.tables_must_exist {
+            for (name, span) in &other.names {
+                if !schemas.borrow_schemas().schemas.contains_key(name.as_str()) {
+                    issues.push(sql_type::Issue::err(
+                        format!("Unknown table {}", name),
+                        span,
+                    ));
+                }
+            }
+        }
+    }
+    let other_select = other.as_mut().and_then(|other| other.select.take());
+    let mut other_arguments = Vec::new();
+    if let Some(select) = &other_select {
+        // Unnamed columns are expected when typing expressions as a select
+        let type_options = Options {
+            warn_unnamed_column_in_select: false,
+            warn_duplicate_column_in_select: false,
+            ..options.clone()
+        }
+        .type_options()
+        .arguments(argument_style.sql_arguments());
+        let stmt = py.allow_threads(|| {
+            catch_type_statement(
+                schemas.borrow_schemas(),
+                statement,
+                select,
+                &mut issues,
+                &type_options,
+            )
+        });
+        if let Some(sql_type::StatementType::Select { arguments, .. }) = stmt {
+            let start = select.len() - select.trim_start().len();
+            other_arguments = shift_arguments(arguments, placeholders_before(&placeholders, start));
+        }
+    }
+
+    let returning_src = match (&returning, &stmt, targets.tables.as_slice()) {
+        (
+            Some(r),
+            sql_type::StatementType::Insert { .. }
+            | sql_type::StatementType::Replace { .. }
+            | sql_type::StatementType::Delete { .. },
+            [table],
+        ) => Some(returning_select(&src, r, table)),
+        (
+            Some(r),
+            sql_type::StatementType::Insert { .. }
+            | sql_type::StatementType::Replace { .. }
+            | sql_type::StatementType::Delete { .. },
+            _,
+        ) => {
+            issues.push(sql_type::Issue::err(
+                "RETURNING is only supported on a single table",
+                r,
+            ));
+            None
+        }
+        _ => None,
+    };
+    let (returning_columns, returning_arguments) = match &returning_src {
+        Some(returning_src) => match py.allow_threads(|| {
+            let stmt = catch_type_statement(
+                schemas.borrow_schemas(),
+                statement,
+                returning_src,
+                &mut issues,
+                &type_options,
+            );
+            // Any parse errors are reported by sql_type above
+            let parsed = match stmt {
+                Some(_) => parse_statement(returning_src, &mut Vec::new(), &parse_options),
+                None => None,
+            };
+            (stmt, parsed)
+        }) {
+            (Some(sql_type::StatementType::Select { columns, arguments }), parsed) => {
+                let columns = map_columns(py, schemas, parsed.as_ref(), columns)?;
+                // Arguments in the clause are numbered after those before it
+                let offset = returning
+                    .as_ref()
+                    .map_or(0, |r| placeholders_before(&placeholders, r.start));
+                (columns, shift_arguments(arguments, offset))
+            }
+            _ => (Vec::new(), Vec::new()),
+        },
+        None => (Vec::new(), Vec::new()),
+    };
 
     let res = match stmt {
         sql_type::StatementType::Select { columns, arguments } => {
-            let columns = columns
-                .into_iter()
-                .map(|v| {
-                    (
-                        v.name.map(|v| v.to_string()),
-                        map_type(v.type_.t),
-                        v.type_.not_null,
-                    )
-                })
-                .collect();
+            let (arguments, signature) = map_arguments(py, &placeholders, arguments, &mut issues)?;
+            let columns = map_columns(py, schemas, parsed.as_ref(), columns)?;
             Py::new(
                 py,
-                Select {
-                    arguments: map_arguments(arguments),
-                    columns,
-                },
+                (
+                    Select {
+                        arguments,
+                        signature,
+                        argument_style,
+                        columns,
+                    },
+                    Statement {
+                        kind: "select",
+                        attributes: &["columns", "arguments", "signature", "argument_style"],
+                    },
+                ),
+            )?
+            .to_object(py)
+        }
+        sql_type::StatementType::Delete { arguments } => {
+            let arguments = arguments.into_iter().chain(returning_arguments).collect();
+            let (arguments, signature) = map_arguments(py, &placeholders, arguments, &mut issues)?;
+            Py::new(
+                py,
+                (
+                    Delete {
+                        tables: targets.tables,
+                        has_where: targets.has_where,
+                        columns: returning_columns,
+                        arguments,
+                        signature,
+                        argument_style,
+                    },
+                    Statement {
+                        kind: "delete",
+                        attributes: &[
+                            "tables",
+                            "has_where",
+                            "columns",
+                            "arguments",
+                            "signature",
+                            "argument_style",
+                        ],
+                    },
+                ),
             )?
             .to_object(py)
         }
-        sql_type::StatementType::Delete { arguments } => Py::new(
-            py,
-            Delete {
-                arguments: map_arguments(arguments),
-            },
-        )?
-        .to_object(py),
         sql_type::StatementType::Insert {
             yield_autoincrement,
             arguments,
         } => {
-            let yield_autoincrement = match yield_autoincrement {
-                sql_type::AutoIncrementId::Yes => "yes",
-                sql_type::AutoIncrementId::No => "no",
-                sql_type::AutoIncrementId::Optional => "maybe",
-            };
+            let yield_autoincrement = yield_autoincrement.into();
+            let arguments = arguments.into_iter().chain(returning_arguments).collect();
+            let (arguments, signature) = map_arguments(py, &placeholders, arguments, &mut issues)?;
             Py::new(
                 py,
-                Insert {
-                    yield_autoincrement,
-                    arguments: map_arguments(arguments),
-                },
+                (
+                    Insert {
+                        yield_autoincrement,
+                        tables: targets.tables,
+                        assigned_columns: targets.assigned_columns,
+                        on_duplicate_key_update: targets.on_duplicate_key_update,
+                        columns: returning_columns,
+                        arguments,
+                        signature,
+                        argument_style,
+                    },
+                    Statement {
+                        kind: "insert",
+                        attributes: &[
+                            "yield_autoincrement",
+                            "tables",
+                            "assigned_columns",
+                            "on_duplicate_key_update",
+                            "columns",
+                            "arguments",
+                            "signature",
+                            "argument_style",
+                        ],
+                    },
+                ),
             )?
             .to_object(py)
         }
-        sql_type::StatementType::Update { arguments } => Py::new(
-            py,
-            Update {
-                arguments: map_arguments(arguments),
-            },
-        )?
-        .to_object(py),
-        sql_type::StatementType::Replace { arguments } => Py::new(
-            py,
-            Replace {
-                arguments: map_arguments(arguments),
-            },
-        )?
-        .to_object(py),
-        sql_type::StatementType::Invalid => Py::new(py, Invalid {})?.to_object(py),
+        sql_type::StatementType::Update { arguments } => {
+            let (arguments, signature) = map_arguments(py, &placeholders, arguments, &mut issues)?;
+            Py::new(
+                py,
+                (
+                    Update {
+                        tables: targets.tables,
+                        assigned_columns: targets.assigned_columns,
+                        has_where: targets.has_where,
+                        arguments,
+                        signature,
+                        argument_style,
+                    },
+                    Statement {
+                        kind: "update",
+                        attributes: &[
+                            "tables",
+                            "assigned_columns",
+                            "has_where",
+                            "arguments",
+                            "signature",
+                            "argument_style",
+                        ],
+                    },
+                ),
+            )?
+            .to_object(py)
+        }
+        sql_type::StatementType::Replace { arguments } => {
+            let arguments = arguments.into_iter().chain(returning_arguments).collect();
+            let (arguments, signature) = map_arguments(py, &placeholders, arguments, &mut issues)?;
+            Py::new(
+                py,
+                (
+                    Replace {
+                        tables: targets.tables,
+                        assigned_columns: targets.assigned_columns,
+                        columns: returning_columns,
+                        arguments,
+                        signature,
+                        argument_style,
+                    },
+                    Statement {
+                        kind: "replace",
+                        attributes: &[
+                            "tables",
+                            "assigned_columns",
+                            "columns",
+                            "arguments",
+                            "signature",
+                            "argument_style",
+                        ],
+                    },
+                ),
+            )?
+            .to_object(py)
+        }
+        sql_type::StatementType::Invalid => match other {
+            Some(other) => {
+                let (arguments, signature) =
+                    map_arguments(py, &placeholders, other_arguments, &mut issues)?;
+                map_other(py, other, arguments, signature, argument_style)?
+            }
+            None => Py::new(
+                py,
+                (
+                    Invalid {},
+                    Statement {
+                        kind: "invalid",
+                        attributes: &[],
+                    },
+                ),
+            )?
+            .to_object(py),
+        },
     };
 
-    let (err, messages) = issues_to_string("", statement, issues);
-    Ok((res, err, messages))
+    let files = [SourceFile {
+        name: std::string::String::new(),
+        range: 0..statement.len(),
+    }];
+    let sources = Sources::new(statement, &files);
+    let (err, messages) = issues_to_string(&sources, &issues);
+    let issues = map_issues(&sources, &issues);
+    Ok((res, err, messages, issues))
+}
+
+/// Like type_statement but raise SqlTypeError on errors, returning the warnings
+#[pyfunction("*", dialect = "None", argument_style = "None", options = "None")]
+#[pyo3(
+    text_signature = "(schemas, statement, dict_result, *, dialect=None, argument_style=None, options=None)"
+)]
+fn type_statement_strict(
+    py: Python,
+    schemas: &Schemas,
+    statement: &str,
+    dict_result: bool,
+    dialect: Option<Dialect>,
+    argument_style: Option<ArgumentStyle>,
+    options: Option<Options>,
+) -> PyResult<(PyObject, Vec<Issue>)> {
+    let (stmt, err, messages, issues) = type_statement(
+        py,
+        schemas,
+        statement,
+        dict_result,
+        dialect,
+        argument_style,
+        options,
+    )?;
+    if err {
+        return Err(sql_type_error(py, messages, issues));
+    }
+    Ok((stmt, issues))
 }
 
 #[pymodule]
-fn mysql_type_plugin(_py: Python, m: &PyModule) -> PyResult<()> {
+fn mysql_type_plugin(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse_schemas, m)?)?;
     m.add_function(wrap_pyfunction!(type_statement, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_schemas_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_schemas_files, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_schemas_files_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(type_statement_strict, m)?)?;
+    m.add("SqlTypeError", py.get_type::<SqlTypeError>())?;
+    m.add_class::<Statement>()?;
     m.add_class::<Select>()?;
     m.add_class::<Delete>()?;
     m.add_class::<Insert>()?;
     m.add_class::<Update>()?;
     m.add_class::<Replace>()?;
     m.add_class::<Invalid>()?;
+    m.add_class::<Create>()?;
+    m.add_class::<Alter>()?;
+    m.add_class::<Drop>()?;
+    m.add_class::<Truncate>()?;
+    m.add_class::<SetVariables>()?;
+    m.add_class::<Call>()?;
+    m.add_class::<LockTables>()?;
+    m.add_class::<UnlockTables>()?;
+    m.add_class::<Transaction>()?;
+    m.add_class::<SqlType>()?;
     m.add_class::<Integer>()?;
     m.add_class::<Bool>()?;
     m.add_class::<Any>()?;
     m.add_class::<Float>()?;
     m.add_class::<Bytes>()?;
     m.add_class::<String>()?;
+    m.add_class::<Date>()?;
+    m.add_class::<DateTime>()?;
+    m.add_class::<Time>()?;
+    m.add_class::<Timestamp>()?;
+    m.add_class::<Json>()?;
     m.add_class::<Enum>()?;
+    m.add_class::<Set>()?;
+    m.add_class::<List>()?;
     m.add_class::<Schemas>()?;
+    m.add_class::<Dialect>()?;
+    m.add_class::<ArgumentStyle>()?;
+    m.add_class::<Options>()?;
+    m.add_class::<Argument>()?;
+    m.add_class::<Signature>()?;
+    m.add_class::<Column>()?;
+    m.add_class::<AutoIncrement>()?;
+    m.add_class::<Level>()?;
+    m.add_class::<Fragment>()?;
+    m.add_class::<Issue>()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, range: std::ops::Range<usize>) -> SourceFile {
+        SourceFile {
+            name: name.to_string(),
+            range,
+        }
+    }
+
+    /// The types of the columns of table t in schema
+    fn column_types(schema: &str) -> Vec<std::string::String> {
+        let mut issues = Vec::new();
+        let schemas = sql_type::schema::parse_schemas(schema, &mut issues, &TypeOptions::new());
+        assert!(issues.is_empty(), "{:?}", issues);
+        schemas.schemas["t"]
+            .columns
+            .iter()
+            .map(|c| map_type(c.type_.t.clone()).describe())
+            .collect()
+    }
+
+    #[test]
+    fn date_and_time_types_are_distinct() {
+        assert_eq!(
+            column_types("CREATE TABLE t (a date, b datetime, c time, d timestamp);"),
+            vec!["date", "datetime", "time", "timestamp"]
+        );
+    }
+
+    /// The types of the columns of a select against schema
+    fn select_types(schema: &str, statement: &str) -> Vec<std::string::String> {
+        let mut issues = Vec::new();
+        let schemas = sql_type::schema::parse_schemas(schema, &mut issues, &TypeOptions::new());
+        match sql_type::type_statement(&schemas, statement, &mut issues, &TypeOptions::new()) {
+            sql_type::StatementType::Select { columns, .. } => {
+                assert!(issues.is_empty(), "{:?}", issues);
+                columns
+                    .into_iter()
+                    .map(|c| map_type(c.type_.t).describe())
+                    .collect()
+            }
+            _ => panic!("{:?} is not a select", statement),
+        }
+    }
+
+    #[test]
+    fn json_functions_are_json() {
+        assert_eq!(
+            select_types(
+                "CREATE TABLE t (a text);",
+                "SELECT JSON_EXTRACT(a, '$.x'), JSON_VALUE(a, '$.y'), a FROM t"
+            ),
+            vec!["json", "json", "string"]
+        );
+    }
+
+    #[test]
+    fn set_and_enum_keep_their_values() {
+        assert_eq!(
+            column_types("CREATE TABLE t (a set('x','y'), b enum('p','q','r'));"),
+            vec!["set('x','y')", "enum('p','q','r')"]
+        );
+    }
+
+    #[test]
+    fn integers_keep_width_and_signedness() {
+        assert_eq!(
+            column_types(
+                "CREATE TABLE t (a tinyint, b smallint unsigned, c int, d int unsigned, e bigint);"
+            ),
+            vec!["int8", "uint16", "int32", "uint32", "int64"]
+        );
+    }
+
+    #[test]
+    fn floats_keep_their_precision() {
+        assert_eq!(
+            column_types("CREATE TABLE t (a float, b double);"),
+            vec!["float32", "float64"]
+        );
+    }
+
+    #[test]
+    fn issues_are_located_in_their_file() {
+        let src = "SELECT\n  é, b";
+        let files = [file("statement", 0..src.len())];
+        let issues = [sql_type::Issue::warn("Bad", &(13..14)).frag("First", &(9..11))];
+        let issues = map_issues(&Sources::new(src, &files), &issues);
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert!(issue.level == Level::Warning);
+        assert_eq!(issue.message, "Bad");
+        assert_eq!(issue.file, "statement");
+        assert_eq!(issue.span, (13, 14));
+        assert_eq!((issue.line, issue.column), (2, 6));
+        assert_eq!(issue.fragments.len(), 1);
+        let fragment = &issue.fragments[0];
+        assert_eq!(fragment.message, "First");
+        assert_eq!(fragment.span, (9, 11));
+        assert_eq!((fragment.line, fragment.column), (2, 3));
+    }
+
+    fn schema_files(files: &[(&str, &str)]) -> (Schemas, Vec<sql_type::Issue>) {
+        let mut issues = Vec::new();
+        let sources = files
+            .iter()
+            .map(|(name, text)| (name.to_string(), text.to_string()))
+            .collect();
+        let schemas = build_schemas(Dialect::MariaDB, sources, &mut issues, &TypeOptions::new());
+        (schemas, issues)
+    }
+
+    #[test]
+    fn schemas_list_their_tables_and_files() {
+        let (schemas, issues) = schema_files(&[
+            (
+                "a.sql",
+                "CREATE TABLE t (id int); CREATE VIEW v AS SELECT id FROM t;",
+            ),
+            ("b.sql", "CREATE TABLE u (id int);"),
+        ]);
+        assert!(issues.is_empty());
+        let mut tables = schemas.tables();
+        tables.sort();
+        assert_eq!(tables, vec!["t", "u", "v"]);
+        assert_eq!(schemas.files(), vec!["a.sql", "b.sql"]);
+    }
+
+    #[test]
+    fn issues_are_located_in_the_file_they_are_in() {
+        let (schemas, issues) = schema_files(&[
+            ("a.sql", "CREATE TABLE t (id int);"),
+            ("b.sql", "-- é\nCREATE TABLE u (id intt);"),
+        ]);
+        assert_eq!(issues.len(), 1);
+        let sources = Sources::new(schemas.borrow_src(), schemas.borrow_files());
+        assert_eq!(sources.locate(&issues[0].span), (1, 25..29));
+        assert_eq!(sources.char_span(&issues[0].span), (1, 24..28));
+        let issue = &map_issues(&sources, &issues)[0];
+        assert_eq!(issue.file, "b.sql");
+        assert_eq!(issue.span, (25, 29));
+        assert_eq!((issue.line, issue.column), (2, 20));
+        let (err, report) = issues_to_string(&sources, &issues);
+        assert!(err);
+        assert!(report.contains("b.sql:2:20"));
+
+        assert_eq!(sources.locate(&(3..5)), (0, 3..5));
+        assert_eq!(line_column(sources.text(1), 5), (1, 5));
+    }
+
+    /// Type statement against schema, returning its ordered arguments and the issues
+    fn arguments(schema: &str, statement: &str) -> (Vec<TypedArgument>, Vec<sql_type::Issue>) {
+        let options = TypeOptions::new().arguments(SQLArguments::Percent);
+        let mut issues = Vec::new();
+        let schemas = sql_type::schema::parse_schemas(schema, &mut issues, &options);
+        assert!(issues.is_empty());
+        let placeholders = placeholders::placeholders(statement, ArgumentStyle::Percent, true);
+        let src = placeholders::rewrite(statement, ArgumentStyle::Percent, &placeholders);
+        let arguments = match sql_type::type_statement(&schemas, &src, &mut issues, &options) {
+            sql_type::StatementType::Select { arguments, .. } => arguments,
+            _ => panic!("{:?} is not a select", statement),
+        };
+        (order_arguments(&placeholders, arguments), issues)
+    }
+
+    fn keys_and_types(arguments: &[TypedArgument]) -> Vec<(ArgumentKey, std::string::String)> {
+        arguments
+            .iter()
+            .map(|a| (a.key.clone(), a.type_.describe()))
+            .collect()
+    }
+
+    const SCHEMA: &str = "CREATE TABLE t (id int NOT NULL, a varchar(10), b bigint unsigned);";
+
+    #[test]
+    fn arguments_are_ordered_by_placeholder() {
+        let (arguments, issues) = arguments(
+            SCHEMA,
+            "SELECT id FROM t WHERE b=%s AND %s IS NULL AND a=%s",
+        );
+        assert!(issues.is_empty());
+        assert_eq!(
+            keys_and_types(&arguments),
+            vec![
+                (ArgumentKey::Index(0), "integer".to_string()),
+                (ArgumentKey::Index(1), "any".to_string()),
+                (ArgumentKey::Index(2), "string".to_string()),
+            ]
+        );
+        let spans: Vec<_> = arguments.iter().map(|a| a.span.clone()).collect();
+        assert_eq!(spans, vec![Some(25..27), Some(32..34), Some(49..51)]);
+    }
+
+    #[test]
+    fn list_arguments_are_typed_as_lists() {
+        let (arguments, issues) = arguments(
+            SCHEMA,
+            "SELECT id FROM t WHERE b=%s AND id IN (_LIST_) AND a=%s",
+        );
+        assert!(issues.is_empty());
+        assert_eq!(
+            keys_and_types(&arguments),
+            vec![
+                (ArgumentKey::Index(0), "integer".to_string()),
+                (ArgumentKey::Index(1), "list of integer".to_string()),
+                (ArgumentKey::Index(2), "string".to_string()),
+            ]
+        );
+        assert_eq!(arguments[1].span, Some(39..45));
+        assert!(arguments[1].not_null);
+    }
+
+    #[test]
+    fn returning_arguments_follow_those_before_the_clause() {
+        let statement = "INSERT INTO t (a) VALUES (%s) RETURNING id, b + %s";
+        let options = TypeOptions::new().arguments(SQLArguments::Percent);
+        let mut issues = Vec::new();
+        let schemas = sql_type::schema::parse_schemas(SCHEMA, &mut issues, &options);
+        let placeholders = placeholders::placeholders(statement, ArgumentStyle::Percent, true);
+        let returning = placeholders::returning(statement).expect("statement has RETURNING");
+        let select = returning_select(statement, &returning, "t");
+        assert_eq!(&select[..returning.start], " ".repeat(returning.start));
+        assert_eq!(&select[returning.start..], "SELECT    id, b + %s FROM `t`");
+        let arguments = match sql_type::type_statement(&schemas, &select, &mut issues, &options) {
+            sql_type::StatementType::Select { arguments, .. } => arguments,
+            _ => panic!("{:?} is not a select", select),
+        };
+        assert!(issues.is_empty(), "{:?}", issues);
+        let offset = placeholders_before(&placeholders, returning.start);
+        assert_eq!(offset, 1);
+        let arguments = order_arguments(&placeholders, shift_arguments(arguments, offset));
+        assert_eq!(
+            keys_and_types(&arguments),
+            vec![
+                (ArgumentKey::Index(0), "any".to_string()),
+                (ArgumentKey::Index(1), "integer".to_string()),
+            ]
+        );
+        assert_eq!(arguments[1].span, Some(48..50));
+
+        assert_eq!(
+            &returning_select(statement, &returning, "db.t")[returning.start..],
+            "SELECT    id, b + %s FROM `db`.`t`"
+        );
+    }
+
+    #[test]
+    fn inserts_yield_an_auto_increment_id() {
+        let schema = "CREATE TABLE t (id int NOT NULL AUTO_INCREMENT, a int, PRIMARY KEY (id));
+            CREATE TABLE u (a int);";
+        let options = TypeOptions::new();
+        let mut issues = Vec::new();
+        let schemas = sql_type::schema::parse_schemas(schema, &mut issues, &options);
+        let yields = |statement: &str| match sql_type::type_statement(
+            &schemas,
+            statement,
+            &mut Vec::new(),
+            &options,
+        ) {
+            sql_type::StatementType::Insert {
+                yield_autoincrement,
+                ..
+            } => AutoIncrement::from(yield_autoincrement),
+            _ => panic!("{:?} is not an insert", statement),
+        };
+        assert!(yields("INSERT INTO t (a) VALUES (1)") == AutoIncrement::Yes);
+        assert!(yields("INSERT INTO u (a) VALUES (1)") == AutoIncrement::No);
+        assert!(yields("INSERT IGNORE INTO t (a) VALUES (1)") == AutoIncrement::Maybe);
+        assert!(
+            yields("INSERT INTO t (a) VALUES (1) ON DUPLICATE KEY UPDATE a = 2")
+                == AutoIncrement::Maybe
+        );
+    }
+
+    #[test]
+    fn named_argument_used_twice_is_merged() {
+        let (arguments, mut issues) = arguments(
+            SCHEMA,
+            "SELECT a FROM t WHERE id=%(x)s OR %(x)s IS NULL OR id+1=%(x)s",
+        );
+        assert_eq!(arguments.len(), 3);
+        let merged = merge_arguments(&arguments, &mut issues);
+        assert!(issues.is_empty());
+        assert_eq!(
+            keys_and_types(&merged),
+            vec![(
+                ArgumentKey::Identifier("x".to_string()),
+                "integer".to_string()
+            )]
+        );
+        assert_eq!(merged[0].span, Some(25..30));
+    }
+
+    #[test]
+    fn named_argument_used_as_different_types_is_an_error() {
+        let statement = "SELECT id FROM t WHERE a=%(x)s OR id=%(x)s";
+        let (arguments, mut issues) = arguments(SCHEMA, statement);
+        let merged = merge_arguments(&arguments, &mut issues);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "Argument x is used as both string and integer"
+        );
+        assert_eq!(issues[0].span, 37..42);
+        assert_eq!(
+            issues[0].fragments,
+            vec![("Used as string here".to_string(), 25..30)]
+        );
+    }
+
+    #[test]
+    fn empty_statement_is_reported_without_ariadne() {
+        let schemas = sql_type::schema::Schemas {
+            schemas: Default::default(),
+            procedures: Default::default(),
+            functions: Default::default(),
+        };
+        let mut issues = Vec::new();
+        sql_type::type_statement(&schemas, "", &mut issues, &TypeOptions::new());
+        assert!(!issues.is_empty());
+        let files = [file("statement", 0..0)];
+        let (err, report) = issues_to_string(&Sources::new("", &files), &issues);
+        assert!(err);
+        assert!(report.starts_with("statement:1:1: Error: "));
+    }
+
+    #[test]
+    fn issue_in_empty_schema_file_is_reported_without_ariadne() {
+        let src = format!("CREATE TABLE t (id int);{}", FILE_SEPARATOR);
+        let files = [file("a.sql", 0..24), file("b.sql", src.len()..src.len())];
+        let issues = [sql_type::Issue::err("Broken", &(src.len()..src.len()))];
+        let (err, report) = issues_to_string(&Sources::new(&src, &files), &issues);
+        assert!(err);
+        assert_eq!(report, "b.sql:1:1: Error: Broken\n");
+    }
+}
@@ -0,0 +1,193 @@
+//! The options object shared by parse_schemas and type_statement
+
+// The wrapper pyo3 generates for #[new] trips this lint on newer compilers
+#![allow(non_local_definitions)]
+
+use pyo3::{basic::CompareOp, prelude::*};
+use sql_type::TypeOptions;
+
+use crate::{attributes_hash, attributes_repr, attributes_richcmp, ArgumentStyle, Dialect};
+
+/// Options for parsing schemas and typing statements, built once and reused
+#[pyclass(
+    text_signature = "(*, dialect=None, argument_style=None, warn_unquoted_identifiers=False, warn_none_capital_keywords=False, warn_unnamed_column_in_select=False, warn_duplicate_column_in_select=False, list_hack=True)"
+)]
+#[derive(Clone)]
+pub(crate) struct Options {
+    /// Dialect of the schemas, MariaDB when None
+    #[pyo3(get)]
+    pub(crate) dialect: Option<Dialect>,
+
+    #[pyo3(get)]
+    pub(crate) argument_style: ArgumentStyle,
+
+    #[pyo3(get)]
+    pub(crate) warn_unquoted_identifiers: bool,
+
+    #[pyo3(get)]
+    pub(crate) warn_none_capital_keywords: bool,
+
+    #[pyo3(get)]
+    pub(crate) warn_unnamed_column_in_select: bool,
+
+    #[pyo3(get)]
+    pub(crate) warn_duplicate_column_in_select: bool,
+
+    /// Accept `_LIST_` placeholders for list arguments. They expand to several positional
+    /// arguments, so they cannot be used in statements with `%(name)s` arguments
+    #[pyo3(get)]
+    pub(crate) list_hack: bool,
+}
+
+const OPTIONS_ATTRIBUTES: &[&str] = &[
+    "dialect",
+    "argument_style",
+    "warn_unquoted_identifiers",
+    "warn_none_capital_keywords",
+    "warn_unnamed_column_in_select",
+    "warn_duplicate_column_in_select",
+    "list_hack",
+];
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            dialect: None,
+            argument_style: ArgumentStyle::Percent,
+            warn_unquoted_identifiers: false,
+            warn_none_capital_keywords: false,
+            warn_unnamed_column_in_select: false,
+            warn_duplicate_column_in_select: false,
+            list_hack: true,
+        }
+    }
+}
+
+#[pymethods]
+impl Options {
+    #[new]
+    #[args(
+        "*",
+        dialect = "None",
+        argument_style = "None",
+        warn_unquoted_identifiers = "false",
+        warn_none_capital_keywords = "false",
+        warn_unnamed_column_in_select = "false",
+        warn_duplicate_column_in_select = "false",
+        list_hack = "true"
+    )]
+    fn new(
+        dialect: Option<Dialect>,
+        argument_style: Option<ArgumentStyle>,
+        warn_unquoted_identifiers: bool,
+        warn_none_capital_keywords: bool,
+        warn_unnamed_column_in_select: bool,
+        warn_duplicate_column_in_select: bool,
+        list_hack: bool,
+    ) -> Self {
+        Options {
+            dialect,
+            argument_style: argument_style.unwrap_or(ArgumentStyle::Percent),
+            warn_unquoted_identifiers,
+            warn_none_capital_keywords,
+            warn_unnamed_column_in_select,
+            warn_duplicate_column_in_select,
+            list_hack,
+        }
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<std::string::String> {
+        attributes_repr(slf, OPTIONS_ATTRIBUTES)
+    }
+
+    fn __richcmp__(slf: &PyCell<Self>, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        attributes_richcmp(slf, other, op, OPTIONS_ATTRIBUTES)
+    }
+
+    fn __hash__(slf: &PyCell<Self>) -> PyResult<isize> {
+        attributes_hash(slf, OPTIONS_ATTRIBUTES)
+    }
+}
+
+impl Options {
+    pub(crate) fn resolved_dialect(&self) -> Dialect {
+        self.dialect.unwrap_or(Dialect::MariaDB)
+    }
+
+    /// The sql_type options, without the argument style which only applies to statements
+    pub(crate) fn type_options(&self) -> TypeOptions {
+        TypeOptions::new()
+            .dialect(self.resolved_dialect().sql_dialect())
+            .warn_unquoted_identifiers(self.warn_unquoted_identifiers)
+            .warn_none_capital_keywords(self.warn_none_capital_keywords)
+            .warn_unnamed_column_in_select(self.warn_unnamed_column_in_select)
+            .warn_duplicate_column_in_select(self.warn_duplicate_column_in_select)
+    }
+
+    /// The sql_parse options used to read statements again for details sql_type does not report,
+    /// matching those sql_type parses with so the same issues are found
+    pub(crate) fn parse_options(&self) -> sql_parse::ParseOptions {
+        sql_parse::ParseOptions::new()
+            .dialect(self.resolved_dialect().sql_dialect())
+            .arguments(self.argument_style.sql_arguments())
+            .warn_unquoted_identifiers(self.warn_unquoted_identifiers)
+            .warn_none_capital_keywords(self.warn_none_capital_keywords)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_warnings(options: &Options, statement: &str) -> Vec<String> {
+        let type_options = options.type_options();
+        let mut issues = Vec::new();
+        let schemas =
+            sql_type::schema::parse_schemas("CREATE TABLE t (id INT);", &mut issues, &type_options);
+        let type_options = type_options.arguments(options.argument_style.sql_arguments());
+        sql_type::type_statement(&schemas, statement, &mut issues, &type_options);
+        issues.into_iter().map(|i| i.message).collect()
+    }
+
+    fn parse_messages(options: &Options, statement: &str) -> Vec<String> {
+        let mut issues = Vec::new();
+        sql_parse::parse_statement(statement, &mut issues, &options.parse_options());
+        issues.into_iter().map(|i| i.message).collect()
+    }
+
+    #[test]
+    fn warnings_are_off_by_default() {
+        let options = Options::default();
+        assert!(type_warnings(&options, "select id, id from t").is_empty());
+        assert!(parse_messages(&options, "select id from t").is_empty());
+    }
+
+    #[test]
+    fn warnings_are_passed_to_sql_type_and_sql_parse() {
+        let options = Options {
+            warn_none_capital_keywords: true,
+            warn_duplicate_column_in_select: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            type_warnings(&options, "select id, id from t"),
+            vec![
+                "keyword select should be in ALL CAPS SELECT",
+                "keyword from should be in ALL CAPS FROM",
+                "Multiple columns with the name 'id'",
+            ]
+        );
+        assert!(!parse_messages(&options, "select id from t").is_empty());
+        assert!(type_warnings(&options, "SELECT id FROM t").is_empty());
+    }
+
+    #[test]
+    fn argument_style_is_passed_to_sql_parse() {
+        let options = Options {
+            argument_style: ArgumentStyle::QuestionMark,
+            ..Default::default()
+        };
+        assert!(parse_messages(&options, "SELECT id FROM t WHERE id = ?").is_empty());
+        assert!(!parse_messages(&Options::default(), "SELECT id FROM t WHERE id = ?").is_empty());
+    }
+}
@@ -0,0 +1,403 @@
+//! Lexical scan of a statement for its argument placeholders and clauses
+//!
+//! sql_type numbers arguments in the order the parser meets them but does not
+//! report where they are, so the placeholders are found here by skipping over
+//! strings, quoted identifiers and comments the same way the sql_parse lexer does.
+//! The same scan finds the RETURNING clause, which sql_parse cannot parse yet.
+
+use std::ops::Range;
+
+use crate::ArgumentStyle;
+
+/// Skip past the end of a string quoted with `quote`, starting after the opening quote
+fn skip_string(bytes: &[u8], mut i: usize, quote: u8) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            c if c == quote => {
+                if bytes.get(i + 1) == Some(&quote) {
+                    i += 2;
+                } else {
+                    return i + 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Skip to the end of the line, starting inside a line comment
+fn skip_line(bytes: &[u8], i: usize) -> usize {
+    match bytes[i..].iter().position(|c| matches!(c, b'\r' | b'\n')) {
+        Some(p) => i + p + 1,
+        None => bytes.len(),
+    }
+}
+
+/// Skip past the end of a block comment, starting after the opening `/*`
+fn skip_block_comment(bytes: &[u8], i: usize) -> usize {
+    match bytes[i..].windows(2).position(|w| w == b"*/") {
+        Some(p) => i + p + 2,
+        None => bytes.len(),
+    }
+}
+
+/// An argument placeholder in a statement
+pub(crate) struct Placeholder<'a> {
+    /// Byte span of the placeholder within the statement
+    pub(crate) span: Range<usize>,
+
+    /// Name of a `%(name)s` placeholder
+    pub(crate) name: Option<&'a str>,
+
+    /// True for a `_LIST_` placeholder expanding to a comma separated list of arguments
+    pub(crate) list: bool,
+}
+
+/// The token used for list arguments, for instance in `IN (_LIST_)`
+const LIST: &[u8] = b"_LIST_";
+
+fn is_identifier(c: Option<&u8>) -> bool {
+    matches!(c, Some(b'_' | b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9'))
+}
+
+/// Parse a `%(name)s` placeholder starting at i, returning the name and the end
+fn named(statement: &str, i: usize) -> Option<(&str, usize)> {
+    let rest = statement.get(i + 2..)?;
+    let close = rest.find(')')?;
+    if rest.as_bytes().get(close + 1) != Some(&b's') {
+        return None;
+    }
+    Some((&rest[..close], i + 2 + close + 2))
+}
+
+/// The argument placeholders in statement in textual order
+///
+/// The n'th placeholder is the argument sql_type reports with index n,
+/// once the statement has been passed through [rewrite]. `_LIST_` is only a placeholder
+/// when list is true
+pub(crate) fn placeholders(
+    statement: &str,
+    style: ArgumentStyle,
+    list: bool,
+) -> Vec<Placeholder<'_>> {
+    let bytes = statement.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        i = match (bytes[i], bytes.get(i + 1)) {
+            (b'\'' | b'"' | b'`', _) => skip_string(bytes, i + 1, bytes[i]),
+            (b'-', Some(b'-')) | (b'/', Some(b'/')) => skip_line(bytes, i + 2),
+            (b'/', Some(b'*')) => skip_block_comment(bytes, i + 2),
+            (b'%', Some(b's')) if style == ArgumentStyle::Percent => {
+                spans.push(Placeholder {
+                    span: i..i + 2,
+                    name: None,
+                    list: false,
+                });
+                i + 2
+            }
+            (b'%', Some(b'(')) if style == ArgumentStyle::Percent => match named(statement, i) {
+                Some((name, end)) => {
+                    spans.push(Placeholder {
+                        span: i..end,
+                        name: Some(name),
+                        list: false,
+                    });
+                    end
+                }
+                None => i + 1,
+            },
+            (b'?', _) if style == ArgumentStyle::QuestionMark => {
+                spans.push(Placeholder {
+                    span: i..i + 1,
+                    name: None,
+                    list: false,
+                });
+                i + 1
+            }
+            (b'_', _)
+                if list
+                    && bytes[i..].starts_with(LIST)
+                    && !is_identifier(i.checked_sub(1).and_then(|j| bytes.get(j)))
+                    && !is_identifier(bytes.get(i + LIST.len())) =>
+            {
+                spans.push(Placeholder {
+                    span: i..i + LIST.len(),
+                    name: None,
+                    list: true,
+                });
+                i + LIST.len()
+            }
+            (c, _) if is_identifier(Some(&c)) => {
+                // Skip the rest of the identifier so _LIST_ is only matched as a whole word
+                i + 1
+                    + bytes[i + 1..]
+                        .iter()
+                        .take_while(|c| is_identifier(Some(c)))
+                        .count()
+            }
+            _ => i + 1,
+        };
+    }
+    spans
+}
+
+/// Rewrite named and list placeholders to a single positional one padded with spaces,
+/// so that byte offsets into the result are offsets into statement
+pub(crate) fn rewrite(
+    statement: &str,
+    style: ArgumentStyle,
+    placeholders: &[Placeholder],
+) -> String {
+    let marker = match style {
+        ArgumentStyle::QuestionMark => "?",
+        ArgumentStyle::Percent => "%s",
+    };
+    let mut res = statement.to_string();
+    for p in placeholders.iter().filter(|p| p.name.is_some() || p.list) {
+        let padding = " ".repeat(p.span.len() - marker.len());
+        res.replace_range(p.span.clone(), &format!("{}{}", marker, padding));
+    }
+    res
+}
+
+/// A token of a statement outside of parentheses
+///
+/// Words are identifiers, numbers and keywords. Strings, parenthesized groups and
+/// other punctuation are kept as their source text so trailing tokens can be told apart
+pub(crate) struct Word<'a> {
+    /// Byte span of the word within the statement, including any backticks
+    pub(crate) span: Range<usize>,
+
+    /// The word without backticks
+    pub(crate) text: &'a str,
+
+    /// True for an identifier quoted with backticks, which is never a keyword
+    pub(crate) quoted: bool,
+}
+
+impl Word<'_> {
+    pub(crate) fn is(&self, keyword: &str) -> bool {
+        !self.quoted && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    /// True for a word that may name an object
+    pub(crate) fn is_identifier(&self) -> bool {
+        self.quoted || is_identifier(self.text.as_bytes().first())
+    }
+
+    /// True for a parenthesized group, which may be missing its closing parenthesis
+    pub(crate) fn is_group(&self) -> bool {
+        self.text.starts_with('(')
+    }
+}
+
+/// The tokens of statement outside of parentheses, with each parenthesized group as one token
+pub(crate) fn top_level_words(statement: &str) -> Vec<Word<'_>> {
+    let bytes = statement.as_bytes();
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut group_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let end = match (bytes[i], bytes.get(i + 1)) {
+            (b'`' | b'\'' | b'"', _) => skip_string(bytes, i + 1, bytes[i]),
+            (b'-', Some(b'-')) | (b'/', Some(b'/')) => {
+                i = skip_line(bytes, i + 2);
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                i = skip_block_comment(bytes, i + 2);
+                continue;
+            }
+            (c, _) if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            (b'(', _) => {
+                if depth == 0 {
+                    group_start = i;
+                }
+                depth += 1;
+                i += 1;
+                continue;
+            }
+            (b')', _) if depth > 0 => {
+                depth -= 1;
+                i += 1;
+                if depth == 0 {
+                    words.push(Word {
+                        span: group_start..i,
+                        text: &statement[group_start..i],
+                        quoted: false,
+                    });
+                }
+                continue;
+            }
+            (c, _) if is_identifier(Some(&c)) => {
+                i + 1
+                    + bytes[i + 1..]
+                        .iter()
+                        .take_while(|c| is_identifier(Some(c)))
+                        .count()
+            }
+            _ => i + statement[i..].chars().next().map_or(1, char::len_utf8),
+        };
+        i = end;
+        if depth > 0 {
+            continue;
+        }
+        let quoted = bytes[start] == b'`';
+        let text = match quoted {
+            true => {
+                let text = &statement[start + 1..end];
+                text.strip_suffix('`').unwrap_or(text)
+            }
+            false => &statement[start..end],
+        };
+        words.push(Word {
+            span: start..end,
+            text,
+            quoted,
+        });
+    }
+    if depth > 0 {
+        words.push(Word {
+            span: group_start..bytes.len(),
+            text: &statement[group_start..],
+            quoted: false,
+        });
+    }
+    words
+}
+
+/// Byte span of the RETURNING clause of an INSERT, REPLACE or DELETE statement,
+/// from the keyword up to any trailing semicolon
+pub(crate) fn returning(statement: &str) -> Option<Range<usize>> {
+    let words = top_level_words(statement);
+    let first = words.first()?;
+    if !["INSERT", "REPLACE", "DELETE"].iter().any(|k| first.is(k)) {
+        return None;
+    }
+    let start = words.iter().find(|w| w.is("RETURNING"))?.span.start;
+    let end = statement.trim_end().trim_end_matches(';').trim_end().len();
+    Some(start..end.max(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(statement: &str, style: ArgumentStyle) -> Vec<Range<usize>> {
+        placeholders(statement, style, true)
+            .into_iter()
+            .map(|p| p.span)
+            .collect()
+    }
+
+    #[test]
+    fn placeholders_skip_strings_comments_and_identifiers() {
+        let statement = "SELECT '%s', \"%s\", `%s`, %s -- %s\n/* %s */ FROM t // %s\nWHERE a = %s";
+        assert_eq!(
+            spans(statement, ArgumentStyle::Percent),
+            vec![25..27, 66..68]
+        );
+        assert_eq!(
+            spans("SELECT 'it''s %s', 'a\\'%s', %s", ArgumentStyle::Percent),
+            vec![28..30]
+        );
+    }
+
+    #[test]
+    fn placeholders_follow_the_sql_parse_lexer_on_percent() {
+        // sql_parse lexes %% as a modulo followed by whatever comes next
+        assert_eq!(
+            spans("SELECT a %% b, '100%%', %s", ArgumentStyle::Percent),
+            vec![24..26]
+        );
+        assert_eq!(spans("SELECT a %%s", ArgumentStyle::Percent), vec![10..12]);
+        assert_eq!(spans("SELECT %d, %", ArgumentStyle::Percent), vec![]);
+    }
+
+    #[test]
+    fn placeholders_of_each_style() {
+        let statement = "SELECT ?, %s FROM t WHERE a = '?'";
+        assert_eq!(spans(statement, ArgumentStyle::QuestionMark), vec![7..8]);
+        assert_eq!(spans(statement, ArgumentStyle::Percent), vec![10..12]);
+    }
+
+    #[test]
+    fn named_placeholders() {
+        let statement = "SELECT %(user_id)s, %(x)s, %(broken s";
+        let found = placeholders(statement, ArgumentStyle::Percent, true);
+        let names: Vec<_> = found.iter().map(|p| p.name).collect();
+        assert_eq!(names, vec![Some("user_id"), Some("x")]);
+        assert_eq!(found[0].span, 7..18);
+        assert!(placeholders(statement, ArgumentStyle::QuestionMark, true).is_empty());
+    }
+
+    #[test]
+    fn list_placeholders_are_whole_words() {
+        let statement =
+            "SELECT a FROM t WHERE a IN (_LIST_) AND b_LIST_ = _LIST_c AND c = '_LIST_'";
+        let found = placeholders(statement, ArgumentStyle::Percent, true);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].list);
+        assert_eq!(found[0].span, 28..34);
+        assert!(placeholders(statement, ArgumentStyle::Percent, false).is_empty());
+    }
+
+    #[test]
+    fn rewrite_preserves_offsets() {
+        let statement = "SELECT %(a)s, %s FROM t WHERE b IN (_LIST_)";
+        let found = placeholders(statement, ArgumentStyle::Percent, true);
+        let res = rewrite(statement, ArgumentStyle::Percent, &found);
+        assert_eq!(res, "SELECT %s   , %s FROM t WHERE b IN (%s    )");
+        assert_eq!(res.len(), statement.len());
+
+        let statement = "SELECT ? FROM t WHERE b IN (_LIST_)";
+        let found = placeholders(statement, ArgumentStyle::QuestionMark, true);
+        let res = rewrite(statement, ArgumentStyle::QuestionMark, &found);
+        assert_eq!(res, "SELECT ? FROM t WHERE b IN (?     )");
+    }
+
+    #[test]
+    fn top_level_words_group_parentheses() {
+        let statement = "CALL `my proc`(a, (b)), 'x' -- c\n;";
+        let words = top_level_words(statement);
+        let texts: Vec<_> = words.iter().map(|w| w.text).collect();
+        assert_eq!(texts, vec!["CALL", "my proc", "(a, (b))", ",", "'x'", ";"]);
+        assert!(words[1].quoted && words[1].is_identifier());
+        assert!(words[2].is_group());
+        assert!(!words[4].is_identifier());
+
+        let words = top_level_words("CALL p(a, b");
+        assert_eq!(words.last().map(|w| w.text), Some("(a, b"));
+    }
+
+    #[test]
+    fn returning_clause() {
+        assert_eq!(
+            returning("INSERT INTO t (a) VALUES (1) RETURNING a, b ;"),
+            Some(29..43)
+        );
+        assert_eq!(returning("DELETE FROM t RETURNING *"), Some(14..25));
+        assert_eq!(returning("SELECT 1 AS returning"), None);
+        assert_eq!(
+            returning("INSERT INTO t SELECT * FROM (SELECT 1 RETURNING) x"),
+            None
+        );
+    }
+
+    #[test]
+    fn returning_ignores_quoted_identifiers() {
+        let statement = "INSERT INTO t SET `returning`=%s, name=%s";
+        assert_eq!(returning(statement), None);
+
+        let statement = "INSERT INTO t SET `returning`=%s RETURNING `returning`";
+        assert_eq!(returning(statement), Some(33..54));
+    }
+}
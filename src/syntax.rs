@@ -0,0 +1,850 @@
+//! Details of statements that sql_type does not report, read from the sql_parse syntax tree
+
+use std::ops::Range;
+
+use sql_parse::{Expression, IdentifierPart, OptSpanned, Spanned, Statement, TableReference};
+use sql_type::schema::Schemas;
+
+use crate::placeholders::{top_level_words, Word};
+
+/// Where a column of a select comes from
+pub(crate) struct ColumnSource {
+    /// Table and column of the schema when the expression is a plain column reference
+    pub(crate) table: Option<String>,
+    pub(crate) column: Option<String>,
+
+    /// Byte span of the select expression
+    pub(crate) span: Range<usize>,
+
+    /// True if the column was named with AS
+    pub(crate) alias: bool,
+}
+
+/// A referenced table under the name it is referred to by
+struct Reference<'a> {
+    name: &'a str,
+
+    /// The table as written, qualified with its database if it is, None for subqueries
+    table: Option<String>,
+}
+
+impl Reference<'_> {
+    /// True if the schema table has the column
+    fn has_column(&self, schemas: &Schemas<'_>, column: &str) -> bool {
+        self.table
+            .as_deref()
+            .and_then(|t| schemas.schemas.get(t))
+            .and_then(|s| s.get_column(column))
+            .is_some()
+    }
+}
+
+fn collect_references<'a>(reference: &TableReference<'a>, out: &mut Vec<Reference<'a>>) {
+    match reference {
+        TableReference::Table {
+            identifier, as_, ..
+        } => {
+            if let Some(table) = identifier.last() {
+                out.push(Reference {
+                    name: as_.as_ref().unwrap_or(table).value,
+                    table: Some(qualified_name(identifier)),
+                });
+            }
+        }
+        TableReference::Query { as_, .. } => {
+            if let Some(as_) = as_ {
+                out.push(Reference {
+                    name: as_.value,
+                    table: None,
+                });
+            }
+        }
+        TableReference::Join { left, right, .. } => {
+            collect_references(left, out);
+            collect_references(right, out);
+        }
+    }
+}
+
+/// The table of an unqualified column, if exactly one of the referenced tables has it
+fn column_table<'r>(
+    schemas: &Schemas<'_>,
+    references: &'r [Reference<'_>],
+    column: &str,
+) -> Option<&'r str> {
+    let mut tables = references.iter().filter(|r| r.has_column(schemas, column));
+    match (tables.next(), tables.next()) {
+        (Some(r), None) => r.table.as_deref(),
+        _ => None,
+    }
+}
+
+/// The sources of the columns of a select, in the order sql_type reports the columns
+///
+/// Returns None for statements that are not a plain select, or when a `*`
+/// cannot be expanded because it covers a subquery
+pub(crate) fn select_sources(
+    schemas: &Schemas<'_>,
+    statement: &Statement<'_>,
+) -> Option<Vec<ColumnSource>> {
+    let select = match statement {
+        Statement::Select(select) => select,
+        _ => return None,
+    };
+    let mut references = Vec::new();
+    for reference in select.table_references.iter().flatten() {
+        collect_references(reference, &mut references);
+    }
+
+    let source = |table: Option<&str>, column: &str, span: Range<usize>, alias: bool| {
+        let table = table.filter(|t| {
+            schemas
+                .schemas
+                .get(t)
+                .and_then(|s| s.get_column(column))
+                .is_some()
+        });
+        ColumnSource {
+            table: table.map(|t| t.to_string()),
+            column: table.map(|_| column.to_string()),
+            span,
+            alias,
+        }
+    };
+
+    let expand = |reference: &Reference, span: &Range<usize>, out: &mut Vec<ColumnSource>| {
+        let table = reference.table.as_deref()?;
+        let schema = schemas.schemas.get(table)?;
+        for c in &schema.columns {
+            out.push(source(Some(table), c.identifier, span.clone(), false));
+        }
+        Some(())
+    };
+
+    let mut res = Vec::new();
+    for e in &select.select_exprs {
+        let alias = e.as_.is_some();
+        let span = e.expr.span();
+        match &e.expr {
+            Expression::Identifier(parts) => match parts.as_slice() {
+                [IdentifierPart::Name(col)] => {
+                    let table = column_table(schemas, &references, col.value);
+                    res.push(source(table, col.value, span, alias));
+                }
+                [IdentifierPart::Name(tbl), IdentifierPart::Name(col)] => {
+                    let table = references
+                        .iter()
+                        .find(|r| r.name == tbl.value)
+                        .and_then(|r| r.table.as_deref());
+                    res.push(source(table, col.value, span, alias));
+                }
+                [IdentifierPart::Star(_)] => {
+                    for reference in &references {
+                        expand(reference, &span, &mut res)?;
+                    }
+                }
+                [IdentifierPart::Name(tbl), IdentifierPart::Star(_)] => {
+                    let reference = references.iter().find(|r| r.name == tbl.value)?;
+                    expand(reference, &span, &mut res)?;
+                }
+                _ => res.push(source(None, "", parts.opt_span()?, alias)),
+            },
+            _ => res.push(source(None, "", span, alias)),
+        }
+    }
+    Some(res)
+}
+
+/// What an INSERT, REPLACE, UPDATE or DELETE writes
+#[derive(Default)]
+pub(crate) struct Targets {
+    /// Schema tables written to
+    pub(crate) tables: Vec<String>,
+
+    /// Columns assigned by INSERT, REPLACE or UPDATE
+    pub(crate) assigned_columns: Vec<String>,
+
+    pub(crate) on_duplicate_key_update: bool,
+
+    pub(crate) has_where: bool,
+}
+
+fn qualified_name(identifier: &[sql_parse::Identifier<'_>]) -> String {
+    identifier
+        .iter()
+        .map(|i| i.value)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The tables and columns written by a statement, None for statements that do not write
+///
+/// Tables are named as written, qualified with their database if they are
+pub(crate) fn targets(schemas: &Schemas<'_>, statement: &Statement<'_>) -> Option<Targets> {
+    Some(match statement {
+        Statement::InsertReplace(i) => {
+            let mut assigned_columns: Vec<String> =
+                i.columns.iter().map(|c| c.value.to_string()).collect();
+            for (c, _, _) in i.set.iter().flat_map(|(_, set)| set) {
+                assigned_columns.push(c.value.to_string());
+            }
+            Targets {
+                tables: vec![qualified_name(&i.table)],
+                assigned_columns,
+                on_duplicate_key_update: i.on_duplicate_key_update.is_some(),
+                has_where: false,
+            }
+        }
+        Statement::Update(u) => {
+            let mut references = Vec::new();
+            for reference in &u.tables {
+                collect_references(reference, &mut references);
+            }
+            let mut tables: Vec<String> = Vec::new();
+            let mut assigned_columns = Vec::new();
+            for (target, _) in &u.set {
+                let (table, column) = match target.as_slice() {
+                    [column] => {
+                        let table = match references.as_slice() {
+                            [reference] => reference.table.as_deref(),
+                            _ => column_table(schemas, &references, column.value),
+                        };
+                        (table, column)
+                    }
+                    [.., table, column] => (
+                        references
+                            .iter()
+                            .find(|r| r.name == table.value)
+                            .and_then(|r| r.table.as_deref()),
+                        column,
+                    ),
+                    [] => continue,
+                };
+                if let Some(table) = table {
+                    if !tables.iter().any(|t| t == table) {
+                        tables.push(table.to_string());
+                    }
+                }
+                assigned_columns.push(column.value.to_string());
+            }
+            Targets {
+                tables,
+                assigned_columns,
+                on_duplicate_key_update: false,
+                has_where: u.where_.is_some(),
+            }
+        }
+        Statement::Delete(d) => {
+            let mut references = Vec::new();
+            for reference in &d.using {
+                collect_references(reference, &mut references);
+            }
+            let tables = d
+                .tables
+                .iter()
+                .map(|t| match t.as_slice() {
+                    [name] => references
+                        .iter()
+                        .find(|r| r.name == name.value)
+                        .and_then(|r| r.table.clone())
+                        .unwrap_or_else(|| name.value.to_string()),
+                    _ => qualified_name(t),
+                })
+                .collect();
+            Targets {
+                tables,
+                assigned_columns: Vec::new(),
+                on_duplicate_key_update: false,
+                has_where: d.where_.is_some(),
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Statements sql_type cannot type
+pub(crate) enum OtherKind {
+    Create,
+    Alter,
+    Drop,
+    Truncate,
+    SetVariables,
+    Call,
+    LockTables,
+    UnlockTables,
+    Transaction,
+}
+
+/// A statement sql_type cannot type, with the objects it refers to
+pub(crate) struct Other {
+    pub(crate) kind: OtherKind,
+
+    /// Type of object for CREATE, ALTER and DROP, the action for transaction control
+    pub(crate) object: &'static str,
+
+    /// Names of the referenced objects with their byte spans
+    pub(crate) names: Vec<(String, Range<usize>)>,
+
+    /// True if the named objects are tables that must exist
+    pub(crate) tables_must_exist: bool,
+
+    /// Offset preserving source of a select of the expressions in the statement,
+    /// so sql_type can check them and type their arguments
+    pub(crate) select: Option<String>,
+}
+
+impl Other {
+    fn new(kind: OtherKind, object: &'static str) -> Self {
+        Other {
+            kind,
+            object,
+            names: Vec::new(),
+            tables_must_exist: false,
+            select: None,
+        }
+    }
+
+    fn name(mut self, identifier: &sql_parse::Identifier<'_>) -> Self {
+        self.names
+            .push((identifier.value.to_string(), identifier.span.clone()));
+        self
+    }
+}
+
+/// Offset preserving source of a select of the expressions at spans, which are
+/// separated by commas in the select
+fn select_of(statement: &str, spans: &[Range<usize>]) -> Option<String> {
+    let first = spans.first()?;
+    if statement[first.clone()].trim().is_empty() {
+        return None;
+    }
+    let mut src = " ".repeat(first.start.checked_sub("SELECT ".len())?);
+    src.push_str("SELECT ");
+    for span in spans {
+        if src.len() < span.start {
+            src.push(',');
+            src.push_str(&" ".repeat(span.start - src.len()));
+        }
+        src.push_str(&statement[span.clone()]);
+    }
+    Some(src)
+}
+
+/// Recognize a statement sql_type cannot type from its syntax tree, or lexically
+/// for the statements sql_parse cannot parse at all
+pub(crate) fn other(parsed: Option<&Statement<'_>>, statement: &str) -> Option<Other> {
+    use OtherKind::*;
+    Some(match parsed {
+        Some(Statement::CreateTable(c)) => Other::new(Create, "table").name(&c.identifier),
+        Some(Statement::CreateView(c)) => {
+            let span = c.select.span();
+            Other {
+                select: Some(format!("{}{}", " ".repeat(span.start), &statement[span])),
+                ..Other::new(Create, "view").name(&c.name)
+            }
+        }
+        Some(Statement::CreateTrigger(c)) => Other::new(Create, "trigger").name(&c.name),
+        Some(Statement::CreateFunction(c)) => Other::new(Create, "function").name(&c.name),
+        Some(Statement::AlterTable(a)) => Other {
+            tables_must_exist: a.if_exists.is_none(),
+            ..Other::new(Alter, "table").name(&a.table)
+        },
+        Some(Statement::DropTable(d)) => {
+            let mut other = Other::new(Drop, "table");
+            for table in &d.tables {
+                other = other.name(table);
+            }
+            Other {
+                tables_must_exist: d.if_exists.is_none(),
+                ..other
+            }
+        }
+        Some(Statement::DropView(d)) => {
+            let mut other = Other::new(Drop, "view");
+            for view in &d.views {
+                other = other.name(view);
+            }
+            other
+        }
+        Some(Statement::DropDatabase(d)) => Other::new(Drop, "database").name(&d.database),
+        Some(Statement::DropEvent(d)) => Other::new(Drop, "event").name(&d.event),
+        Some(Statement::DropFunction(d)) => Other::new(Drop, "function").name(&d.function),
+        Some(Statement::DropProcedure(d)) => Other::new(Drop, "procedure").name(&d.procedure),
+        Some(Statement::DropServer(d)) => Other::new(Drop, "server").name(&d.server),
+        Some(Statement::DropTrigger(d)) => Other::new(Drop, "trigger").name(&d.trigger),
+        Some(Statement::Set(s)) => {
+            let mut other = Other::new(SetVariables, "");
+            for (variable, _) in &s.values {
+                other = other.name(variable);
+            }
+            other
+        }
+        Some(_) => return None,
+        None => return other_lexical(statement),
+    })
+}
+
+/// Reads the words of a statement sql_parse cannot parse
+struct Words<'a, 'b> {
+    words: &'b [Word<'a>],
+}
+
+impl<'a, 'b> Words<'a, 'b> {
+    fn peek(&self) -> Option<&'b Word<'a>> {
+        self.words.first()
+    }
+
+    fn next(&mut self) -> Option<&'b Word<'a>> {
+        let (word, rest) = self.words.split_first()?;
+        self.words = rest;
+        Some(word)
+    }
+
+    fn skip_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek().is_some_and(|w| w.is(keyword));
+        if found {
+            self.words = &self.words[1..];
+        }
+        found
+    }
+
+    fn skip_token(&mut self, token: &str) -> bool {
+        let found = self.peek().is_some_and(|w| !w.quoted && w.text == token);
+        if found {
+            self.words = &self.words[1..];
+        }
+        found
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Option<()> {
+        self.skip_keyword(keyword).then_some(())
+    }
+
+    /// A possibly qualified name
+    fn name(&mut self) -> Option<(String, Range<usize>)> {
+        let start = self.peek()?.span.start;
+        let mut parts = Vec::new();
+        loop {
+            let w = self.next().filter(|w| w.is_identifier())?;
+            parts.push(w.text);
+            if !self.skip_token(".") {
+                return Some((parts.join("."), start..w.span.end));
+            }
+        }
+    }
+}
+
+fn call(w: &mut Words, statement: &str, other: &mut Other) -> Option<()> {
+    other.names.push(w.name()?);
+    if let Some(group) = w.peek().filter(|g| g.is_group()) {
+        w.next();
+        // An unclosed group runs to the end of the statement
+        if !group.text.ends_with(')') {
+            return None;
+        }
+        let arguments = group.span.start + 1..group.span.end - 1;
+        other.select = select_of(statement, std::slice::from_ref(&arguments));
+    }
+    Some(())
+}
+
+/// A character set or collation name, quoted or not
+fn charset_name(w: &mut Words) -> Option<()> {
+    w.next()
+        .filter(|v| v.is_identifier() || v.text.starts_with('\''))
+        .map(|_| ())
+}
+
+/// One assignment of a SET, returning the span of the value expression if it has one
+fn set_variable(w: &mut Words, statement: &str, other: &mut Other) -> Option<Option<Range<usize>>> {
+    let start = w.peek()?.span.start;
+    if w.skip_keyword("NAMES") {
+        other
+            .names
+            .push(("NAMES".to_string(), start..start + "NAMES".len()));
+        charset_name(w)?;
+        if w.skip_keyword("COLLATE") {
+            charset_name(w)?;
+        }
+        return Some(None);
+    }
+    let scoped = ["GLOBAL", "SESSION", "LOCAL"]
+        .iter()
+        .any(|k| w.skip_keyword(k));
+    let name_start = w.peek()?.span.start;
+    if !scoped && w.skip_token("@") {
+        w.skip_token("@");
+    }
+    let (_, name) = w.name()?;
+    other.names.push((
+        statement[name_start..name.end].to_string(),
+        name_start..name.end,
+    ));
+    w.skip_token(":");
+    if !w.skip_token("=") {
+        return None;
+    }
+    let value = w.words.split(|v| !v.quoted && v.text == ",").next()?;
+    let span = value.first()?.span.start..value.last()?.span.end;
+    w.words = &w.words[value.len()..];
+    Some(Some(span))
+}
+
+fn set_variables(w: &mut Words, statement: &str, other: &mut Other) -> Option<()> {
+    let mut values = Vec::new();
+    loop {
+        values.extend(set_variable(w, statement, other)?);
+        if !w.skip_token(",") {
+            break;
+        }
+    }
+    other.select = select_of(statement, &values);
+    Some(())
+}
+
+fn lock_tables(w: &mut Words, other: &mut Other) -> Option<()> {
+    if !w.skip_keyword("TABLES") {
+        w.keyword("TABLE")?;
+    }
+    loop {
+        other.names.push(w.name()?);
+        if w.skip_keyword("AS") {
+            w.name()?;
+        } else if w.peek().is_some_and(|a| {
+            a.is_identifier() && !["READ", "WRITE", "LOW_PRIORITY"].iter().any(|k| a.is(k))
+        }) {
+            w.next();
+        }
+        if w.skip_keyword("READ") {
+            w.skip_keyword("LOCAL");
+        } else {
+            w.skip_keyword("LOW_PRIORITY");
+            w.keyword("WRITE")?;
+        }
+        if !w.skip_token(",") {
+            return Some(());
+        }
+    }
+}
+
+/// Recognize the statements sql_parse cannot parse from their words, None for
+/// anything that does not follow the basic form of one of them
+fn other_lexical(statement: &str) -> Option<Other> {
+    use OtherKind::*;
+    let words = top_level_words(statement);
+    let mut w = Words { words: &words };
+    let first = w.next()?;
+    let mut other;
+    if first.is("TRUNCATE") {
+        other = Other {
+            tables_must_exist: true,
+            ..Other::new(Truncate, "table")
+        };
+        w.skip_keyword("TABLE");
+        other.names.push(w.name()?);
+    } else if first.is("SET") {
+        other = Other::new(SetVariables, "");
+        set_variables(&mut w, statement, &mut other)?;
+    } else if first.is("CALL") {
+        other = Other::new(Call, "procedure");
+        call(&mut w, statement, &mut other)?;
+    } else if first.is("LOCK") {
+        other = Other {
+            tables_must_exist: true,
+            ..Other::new(LockTables, "table")
+        };
+        lock_tables(&mut w, &mut other)?;
+    } else if first.is("UNLOCK") {
+        other = Other::new(UnlockTables, "table");
+        if !w.skip_keyword("TABLES") {
+            w.keyword("TABLE")?;
+        }
+    } else if first.is("START") {
+        other = Other::new(Transaction, "start");
+        w.keyword("TRANSACTION")?;
+    } else if first.is("BEGIN") {
+        other = Other::new(Transaction, "start");
+        w.skip_keyword("WORK");
+    } else if first.is("COMMIT") {
+        other = Other::new(Transaction, "commit");
+        w.skip_keyword("WORK");
+    } else if first.is("ROLLBACK") {
+        other = Other::new(Transaction, "rollback");
+        w.skip_keyword("WORK");
+        if w.skip_keyword("TO") {
+            w.skip_keyword("SAVEPOINT");
+            other.names.push(w.name()?);
+        }
+    } else if first.is("SAVEPOINT") {
+        other = Other::new(Transaction, "savepoint");
+        other.names.push(w.name()?);
+    } else if first.is("RELEASE") {
+        other = Other::new(Transaction, "release");
+        w.keyword("SAVEPOINT")?;
+        other.names.push(w.name()?);
+    } else {
+        return None;
+    }
+    w.words.is_empty().then_some(other)
+}
+
+#[cfg(test)]
+mod tests {
+    use sql_parse::ParseOptions;
+
+    use super::*;
+
+    fn options() -> ParseOptions {
+        ParseOptions::new().arguments(sql_parse::SQLArguments::Percent)
+    }
+
+    fn parse(statement: &str) -> Option<Statement<'_>> {
+        sql_parse::parse_statement(statement, &mut Vec::new(), &options())
+    }
+
+    const SCHEMA: &str = "CREATE TABLE t (id int, a int); CREATE TABLE u (id int, b int, c int);";
+
+    fn targets_of(statement: &str) -> Targets {
+        let schemas =
+            sql_type::schema::parse_schemas(SCHEMA, &mut Vec::new(), &sql_type::TypeOptions::new());
+        targets(&schemas, &parse(statement).expect("statement parses")).expect("statement writes")
+    }
+
+    fn other_of(statement: &str) -> Option<Other> {
+        other(parse(statement).as_ref(), statement)
+    }
+
+    fn names(other: &Other) -> Vec<&str> {
+        other.names.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Table, column, span and alias of a column source
+    type Source = (Option<String>, Option<String>, Range<usize>, bool);
+
+    fn sources_of(statement: &str) -> Vec<Source> {
+        let schemas =
+            sql_type::schema::parse_schemas(SCHEMA, &mut Vec::new(), &sql_type::TypeOptions::new());
+        select_sources(&schemas, &parse(statement).expect("statement parses"))
+            .expect("columns have sources")
+            .into_iter()
+            .map(|s| (s.table, s.column, s.span, s.alias))
+            .collect()
+    }
+
+    fn column(table: &str, column: &str, span: Range<usize>, alias: bool) -> Source {
+        (
+            Some(table.to_string()),
+            Some(column.to_string()),
+            span,
+            alias,
+        )
+    }
+
+    #[test]
+    fn select_sources_of_columns() {
+        assert_eq!(
+            sources_of("SELECT t.*, u.b AS x, id + 1, a FROM t JOIN u ON t.id = u.id"),
+            vec![
+                column("t", "id", 7..10, false),
+                column("t", "a", 7..10, false),
+                column("u", "b", 12..15, true),
+                (None, None, 22..28, false),
+                column("t", "a", 30..31, false),
+            ]
+        );
+        assert_eq!(
+            sources_of("SELECT * FROM u"),
+            vec![
+                column("u", "id", 7..8, false),
+                column("u", "b", 7..8, false),
+                column("u", "c", 7..8, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_targets() {
+        let t = targets_of("INSERT INTO t (a, b) VALUES (%s, %s) ON DUPLICATE KEY UPDATE a = 1");
+        assert_eq!(t.tables, vec!["t"]);
+        assert_eq!(t.assigned_columns, vec!["a", "b"]);
+        assert!(t.on_duplicate_key_update);
+        assert!(!t.has_where);
+
+        let t = targets_of("REPLACE INTO t SET a = %s, `b` = 2");
+        assert_eq!(t.assigned_columns, vec!["a", "b"]);
+        assert!(!t.on_duplicate_key_update);
+    }
+
+    #[test]
+    fn update_targets_resolve_aliases() {
+        let t =
+            targets_of("UPDATE t AS x JOIN u ON x.id = u.id SET x.a = 1, u.b = 2 WHERE u.c = %s");
+        assert_eq!(t.tables, vec!["t", "u"]);
+        assert_eq!(t.assigned_columns, vec!["a", "b"]);
+        assert!(t.has_where);
+
+        let t = targets_of("UPDATE t SET a = 1");
+        assert_eq!(t.tables, vec!["t"]);
+        assert!(!t.has_where);
+    }
+
+    #[test]
+    fn update_targets_resolve_unqualified_columns_against_the_schema() {
+        let t = targets_of("UPDATE t JOIN u ON t.id = u.id SET a = 1");
+        assert_eq!(t.tables, vec!["t"]);
+
+        let t = targets_of("UPDATE t JOIN u ON t.id = u.id SET b = 1, a = c");
+        assert_eq!(t.tables, vec!["u", "t"]);
+
+        // Ambiguous and unknown columns do not name a table
+        let t = targets_of("UPDATE t JOIN u ON t.id = u.id SET id = 1, nope = 2");
+        assert!(t.tables.is_empty());
+        assert_eq!(t.assigned_columns, vec!["id", "nope"]);
+    }
+
+    #[test]
+    fn targets_keep_the_database_of_tables() {
+        assert_eq!(targets_of("UPDATE db.t SET a = 1").tables, vec!["db.t"]);
+        assert_eq!(
+            targets_of("UPDATE db.t AS x JOIN u ON x.id = u.id SET x.a = 1").tables,
+            vec!["db.t"]
+        );
+        assert_eq!(
+            targets_of("INSERT INTO db.t (a) VALUES (1)").tables,
+            vec!["db.t"]
+        );
+        assert_eq!(
+            targets_of("DELETE FROM db.t WHERE a = 1").tables,
+            vec!["db.t"]
+        );
+        assert_eq!(
+            targets_of("DELETE x FROM db.t AS x WHERE x.a = 1").tables,
+            vec!["db.t"]
+        );
+    }
+
+    #[test]
+    fn delete_targets() {
+        let t = targets_of("DELETE FROM t WHERE a = %s");
+        assert_eq!(t.tables, vec!["t"]);
+        assert!(t.has_where);
+        assert!(t.assigned_columns.is_empty());
+    }
+
+    #[test]
+    fn select_has_no_targets() {
+        let schemas =
+            sql_type::schema::parse_schemas(SCHEMA, &mut Vec::new(), &sql_type::TypeOptions::new());
+        assert!(targets(&schemas, &parse("SELECT a FROM t").unwrap()).is_none());
+    }
+
+    #[test]
+    fn other_from_syntax_tree() {
+        let o = other_of("DROP TABLE IF EXISTS a, b").unwrap();
+        assert!(matches!(o.kind, OtherKind::Drop));
+        assert_eq!(o.object, "table");
+        assert_eq!(names(&o), vec!["a", "b"]);
+        assert!(!o.tables_must_exist);
+
+        let o = other_of("CREATE VIEW v AS SELECT a FROM t").unwrap();
+        assert_eq!(names(&o), vec!["v"]);
+        assert_eq!(
+            o.select.as_deref(),
+            Some("                 SELECT a FROM t")
+        );
+
+        assert!(other_of("SELECT 1").is_none());
+    }
+
+    #[test]
+    fn truncate() {
+        let o = other_of("TRUNCATE TABLE t").unwrap();
+        assert!(matches!(o.kind, OtherKind::Truncate));
+        assert_eq!(names(&o), vec!["t"]);
+        assert!(o.tables_must_exist);
+
+        assert!(other_of("TRUNCATE db.t").is_some());
+        assert!(other_of("TRUNCATE TABLE t garbage 'x'").is_none());
+        assert!(other_of("TRUNCATE").is_none());
+    }
+
+    #[test]
+    fn call_arguments_become_a_select() {
+        let o = other_of("CALL db.proc(%s, 1)").unwrap();
+        assert_eq!(names(&o), vec!["db.proc"]);
+        assert_eq!(o.select.as_deref(), Some("      SELECT %s, 1"));
+
+        let o = other_of("CALL proc()").unwrap();
+        assert!(o.select.is_none());
+
+        assert!(other_of("CALL proc(%s").is_none());
+        assert!(other_of("CALL proc(%s) x").is_none());
+    }
+
+    #[test]
+    fn lock_tables() {
+        let o = other_of("LOCK TABLES a READ LOCAL, b AS c LOW_PRIORITY WRITE, d e WRITE").unwrap();
+        assert!(matches!(o.kind, OtherKind::LockTables));
+        assert_eq!(names(&o), vec!["a", "b", "d"]);
+
+        assert!(other_of("LOCK TABLES a").is_none());
+        assert!(other_of("UNLOCK TABLES").is_some());
+        assert!(other_of("UNLOCK TABLES a").is_none());
+    }
+
+    #[test]
+    fn transactions() {
+        for statement in [
+            "START TRANSACTION",
+            "BEGIN WORK",
+            "COMMIT",
+            "ROLLBACK WORK",
+            "SAVEPOINT s",
+            "RELEASE SAVEPOINT s",
+        ] {
+            let o = other_of(statement).unwrap();
+            assert!(matches!(o.kind, OtherKind::Transaction), "{}", statement);
+        }
+
+        let o = other_of("ROLLBACK TO SAVEPOINT `s`").unwrap();
+        assert_eq!(o.object, "rollback");
+        assert_eq!(names(&o), vec!["s"]);
+
+        for statement in [
+            "START TRANSACTION READ ONLY",
+            "COMMIT AND CHAIN",
+            "COMMIT garbage garbage",
+            "RELEASE s",
+        ] {
+            assert!(other_of(statement).is_none(), "{}", statement);
+        }
+    }
+
+    #[test]
+    fn alter_table_from_syntax_tree_only() {
+        // sql_parse only ends the specification list of ALTER TABLE at a delimiter
+        let o = other_of("ALTER TABLE t ADD INDEX i (a);").unwrap();
+        assert!(matches!(o.kind, OtherKind::Alter));
+        assert_eq!(names(&o), vec!["t"]);
+        assert!(o.tables_must_exist);
+
+        assert!(other_of("ALTER TABLE t ADD COLUMN y int").is_none());
+    }
+
+    #[test]
+    fn set_variables() {
+        let statement = "SET NAMES 'utf8mb4' COLLATE utf8mb4_bin, @a := %s, @@session.b = 2";
+        let o = other_of(statement).unwrap();
+        assert!(matches!(o.kind, OtherKind::SetVariables));
+        assert_eq!(names(&o), vec!["NAMES", "@a", "@@session.b"]);
+        let select = o.select.unwrap();
+        assert_eq!(select.len(), statement.len());
+        assert_eq!(&select[40..], "SELECT %s,               2");
+
+        let o = other_of("SET SESSION sql_mode = 'x'").unwrap();
+        assert_eq!(names(&o), vec!["sql_mode"]);
+
+        assert!(other_of("SET @a =").is_none());
+        assert!(other_of("SET CHARACTER SET utf8").is_none());
+    }
+}